@@ -0,0 +1,87 @@
+/*
+ * Compares the compiled assembunny Computer with the interpreters days 12 and 23 used
+ * before they shared one. Every contender parses the program itself.
+ * Run it with `cargo bench --bench assembunny`.
+ */
+use advent_of_code::assembunny::{instructions, Computer};
+use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Shaped like a Day 12 input, run with `c = 1` as in part two
+const PROGRAM: &str = "\
+cpy 1 a
+cpy 1 b
+cpy 27 d
+jnz c 2
+jnz 1 5
+cpy 8 c
+inc d
+dec c
+jnz c -2
+cpy a c
+inc a
+dec b
+jnz b -2
+cpy c b
+dec d
+jnz d -6
+cpy 17 c
+cpy 12 d
+inc a
+dec d
+jnz d -2
+dec c
+jnz c -5";
+
+const RUNS: u32 = 5;
+
+mod day_12;
+mod day_23;
+
+fn compiled(input: &str, c: i32) -> i32 {
+    let mut computer = Computer::new(instructions(input).unwrap().1);
+    computer.set('c', c);
+    computer.run();
+    computer.get('a')
+}
+
+fn optimized(input: &str, c: i32) -> i32 {
+    let mut computer = Computer::optimized(instructions(input).unwrap().1);
+    computer.set('c', c);
+    computer.run();
+    computer.get('a')
+}
+
+fn bench(name: &str, run: impl Fn() -> i32) -> Duration {
+    let expected = run();
+    let timings = (0..RUNS)
+        .map(|_| {
+            let timer = Instant::now();
+            assert_eq!(black_box(run()), expected);
+            timer.elapsed()
+        })
+        .collect::<Vec<_>>();
+    let min = *timings.iter().min().unwrap();
+    let mean = timings.iter().sum::<Duration>() / RUNS;
+    println!(
+        "{ANSI_BOLD}{name:<12}{ANSI_RESET} {expected} {ANSI_ITALIC}(min: {min:.2?}, mean: {mean:.2?}){ANSI_RESET}"
+    );
+    min
+}
+
+fn main() {
+    let day_12 = bench("day 12", || day_12::run(black_box(PROGRAM), 1) as i32);
+    let day_23 = bench("day 23", || day_23::run(black_box(PROGRAM), 1));
+    let compiled = bench("compiled", || compiled(black_box(PROGRAM), 1));
+    let optimized = bench("optimized", || optimized(black_box(PROGRAM), 1));
+
+    println!("---");
+    for (name, baseline) in [("day 12", day_12), ("day 23", day_23)] {
+        println!(
+            "compiled is {:.1}x and optimized {:.0}x as fast as {name}.",
+            baseline.as_secs_f64() / compiled.as_secs_f64(),
+            baseline.as_secs_f64() / optimized.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,274 @@
+/*
+ * Known-good answers for the real inputs, so that refactoring a solution can't silently break it.
+ */
+use crate::days::Options;
+use crate::report::{Format, Record};
+use crate::{manifest_path, parse_file_or_default, InputSource, ANSI_RED, ANSI_RESET, YEAR};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
+
+pub const ANSWERS_PATH: &str = "answers.txt";
+
+/// How an answer compares to the recorded one
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Verdict {
+    Matches,
+    Differs,
+    Unknown,
+}
+
+/// Answers by year, day and part, one `2016/08/2: <answer>` per line. Backslashes and
+/// newlines in answers are escaped, so that every answer fits on its line.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<(u16, u8, u8), String>,
+}
+
+impl Answers {
+    /// No answers if there is no file yet
+    pub fn load(path: &Path) -> io::Result<Answers> {
+        parse_file_or_default(path)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(YEAR, day, part)).map(String::as_str)
+    }
+
+    /// Fills in the expected answer of every record
+    pub fn mark(&self, records: &mut [Record]) {
+        for record in records {
+            record.expected = self.get(record.day, record.part).map(str::to_string);
+        }
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, answer: &str) {
+        self.answers.insert((YEAR, day, part), answer.to_string());
+    }
+
+    /// Overwrites the answers of solved parts
+    pub fn record(&mut self, records: &[Record]) {
+        for record in records {
+            if let Some(answer) = record.answer() {
+                self.insert(record.day, record.part, answer);
+            }
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |line: &str| {
+            io::Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid answer entry \"{line}\""),
+            )
+        };
+        let answers = s
+            .lines()
+            .map(|line| {
+                let (key, answer) = line.split_once(": ").ok_or_else(|| invalid(line))?;
+                let key: Vec<_> = key.split('/').collect();
+                match key[..] {
+                    [year, day, part] => Ok((
+                        (
+                            year.parse().map_err(|_| invalid(line))?,
+                            day.parse().map_err(|_| invalid(line))?,
+                            part.parse().map_err(|_| invalid(line))?,
+                        ),
+                        unescape(answer),
+                    )),
+                    _ => Err(invalid(line)),
+                }
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(Answers { answers })
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for ((year, day, part), answer) in &self.answers {
+            writeln!(f, "{year}/{day:02}/{part}: {}", escape(answer))?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+pub(crate) fn unescape(answer: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                unescaped.push('\\');
+                chars.next();
+            }
+            (c, _) => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+/// Loads the answers file, exiting with the error when it can't be read
+pub fn load() -> Answers {
+    Answers::load(&manifest_path(ANSWERS_PATH)).unwrap_or_else(|e| {
+        eprintln!("{ANSI_RED}error:{ANSI_RESET} could not read \"{ANSWERS_PATH}\": {e}");
+        process::exit(1);
+    })
+}
+
+/// Records the answers and verifies them against the recorded ones, as `options` ask.
+/// Answers for another input than `src/inputs` are neither recorded nor verified.
+/// Returns whether verifying failed because a recorded part didn't give its answer,
+/// or because there was nothing recorded to verify.
+pub fn check(mut answers: Answers, records: &[Record], options: &Options) -> bool {
+    if options.input != InputSource::Default {
+        return false;
+    }
+    let path = &manifest_path(ANSWERS_PATH);
+    let mut failed = false;
+
+    if options.verify {
+        let recorded = records.iter().filter(|record| record.expected.is_some());
+        let count = recorded.clone().count();
+        let changed = recorded
+            .filter(|record| record.verdict() != Verdict::Matches)
+            .count();
+        let summary = match (changed, count) {
+            (_, 0) => format!(
+                "{ANSI_RED}No recorded answers to verify, run with --record first.{ANSI_RESET}"
+            ),
+            (0, _) => format!("All {count} recorded answers match."),
+            _ => format!("{ANSI_RED}{changed} of {count} recorded answers changed.{ANSI_RESET}"),
+        };
+        if options.format == Format::Text {
+            println!("{summary}");
+        } else {
+            eprintln!("{summary}");
+        }
+        failed = changed > 0 || count == 0;
+    }
+
+    if options.record {
+        answers.record(records);
+        if let Err(e) = answers.save(path) {
+            eprintln!("{ANSI_RED}error:{ANSI_RESET} could not write \"{ANSWERS_PATH}\": {e}");
+        }
+    }
+    failed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Outcome;
+    use std::time::Duration;
+
+    #[test]
+    fn test_round_trip() {
+        let mut answers = Answers::default();
+        answers.record(&[
+            Record::new(
+                8,
+                2,
+                Outcome::Solved("#..#\n\\#..".to_string()),
+                Duration::ZERO,
+            ),
+            Record::new(
+                12,
+                2,
+                Outcome::Solved("9227647".to_string()),
+                Duration::ZERO,
+            ),
+            Record::new(14, 2, Outcome::NotSolved, Duration::ZERO),
+        ]);
+        assert_eq!(
+            answers.to_string(),
+            "2016/08/2: #..#\\n\\\\#..\n2016/12/2: 9227647\n"
+        );
+        assert_eq!(answers.to_string().parse::<Answers>().unwrap(), answers);
+        assert!("2016/08: 1".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn test_mark() {
+        let answers: Answers = "2016/12/2: 9227647\n2016/23/2: 479006783".parse().unwrap();
+        let mut records = [
+            Record::new(
+                12,
+                2,
+                Outcome::Solved("9227647".to_string()),
+                Duration::ZERO,
+            ),
+            Record::new(
+                23,
+                2,
+                Outcome::Failed("expected tag".to_string()),
+                Duration::ZERO,
+            ),
+            Record::new(25, 2, Outcome::Solved("38".to_string()), Duration::ZERO),
+        ];
+        answers.mark(&mut records);
+        assert_eq!(
+            records.map(|record| record.verdict()),
+            [Verdict::Matches, Verdict::Differs, Verdict::Unknown]
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let verify = Options {
+            verify: true,
+            ..Options::default()
+        };
+        let mut records = [Record::new(
+            12,
+            2,
+            Outcome::Solved("9227647".to_string()),
+            Duration::ZERO,
+        )];
+        assert!(check(Answers::default(), &records, &verify));
+        records[0].expected = Some("9227647".to_string());
+        assert!(!check(Answers::default(), &records, &verify));
+        records[0].expected = Some("317993".to_string());
+        assert!(check(Answers::default(), &records, &verify));
+    }
+
+    #[test]
+    fn test_check_other_input() {
+        let path = manifest_path(ANSWERS_PATH);
+        let before = fs::read_to_string(&path).ok();
+        let options = Options {
+            record: true,
+            input: InputSource::Path("stress.txt".into()),
+            ..Options::default()
+        };
+        let records = [Record::new(
+            12,
+            2,
+            Outcome::Solved("42".to_string()),
+            Duration::ZERO,
+        )];
+        assert!(!check(load(), &records, &options));
+        assert_eq!(fs::read_to_string(&path).ok(), before);
+    }
+}
@@ -0,0 +1,677 @@
+/*
+ * Talks to the Advent of Code website: downloads inputs and puzzle descriptions.
+ * The base URL can be pointed elsewhere with `AOC_BASE_URL`, e.g. at a local stand-in server.
+ */
+use crate::guesses::Feedback;
+use crate::{manifest_path, puzzle, YEAR};
+use std::cell::Cell;
+use std::env;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+/// The session cookie is read from this variable, or else from a file of this name
+/// in the home directory or the config directory
+pub const SESSION_VAR: &str = "ADVENT_OF_CODE_SESSION";
+pub const SESSION_FILE: &str = ".adventofcode.session";
+
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " by ",
+    env!("CARGO_PKG_AUTHORS")
+);
+
+/// Characters of an error page to keep for error messages
+const ERROR_PAGE_LENGTH: usize = 200;
+
+#[derive(Debug)]
+pub enum AocCliError {
+    SessionNotFound,
+    /// The session cookie wasn't accepted, probably because it expired
+    Unauthorized(ErrorPage),
+    NotUnlocked(ErrorPage),
+    NotFound(ErrorPage),
+    RateLimited(ErrorPage),
+    /// Any other error status
+    Status(ErrorPage),
+    /// A page that doesn't say what it should, like an error page with a success status
+    UnexpectedPage(ErrorPage),
+    Network {
+        url: String,
+        cause: Box<dyn Error + Send + Sync>,
+    },
+    Io {
+        path: PathBuf,
+        cause: io::Error,
+    },
+    PartNotFound(Option<u8>),
+}
+
+impl AocCliError {
+    /// Whether trying again later might work
+    pub fn is_transient(&self) -> bool {
+        match self {
+            AocCliError::RateLimited(_) | AocCliError::Network { .. } => true,
+            AocCliError::Status(page) => page.status >= 500,
+            _ => false,
+        }
+    }
+
+    fn io(path: &Path) -> impl FnOnce(io::Error) -> AocCliError + '_ {
+        |cause| AocCliError::Io {
+            path: path.to_path_buf(),
+            cause,
+        }
+    }
+}
+
+impl Display for AocCliError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AocCliError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set {SESSION_VAR} or paste it into ~/{SESSION_FILE}."
+            ),
+            AocCliError::Unauthorized(_) => write!(
+                f,
+                "the session cookie was not accepted, it may have expired. Paste a fresh one into ~/{SESSION_FILE}."
+            ),
+            AocCliError::NotUnlocked(_) => write!(f, "the puzzle is not unlocked yet."),
+            AocCliError::NotFound(page) => write!(f, "there is no page at {}.", page.url),
+            AocCliError::RateLimited(_) => write!(f, "too many requests, try again later."),
+            AocCliError::Status(page) => write!(f, "{} answered {}.", page.url, page.status),
+            AocCliError::UnexpectedPage(page) => {
+                write!(f, "{} did not answer as expected.", page.url)
+            }
+            AocCliError::Network { url, .. } => write!(f, "could not reach {url}."),
+            AocCliError::Io { path, .. } => write!(f, "could not write \"{}\".", path.display()),
+            AocCliError::PartNotFound(part) => match part {
+                Some(part) => write!(f, "the description has no part {part}. Is it unlocked?"),
+                None => write!(f, "the description has no parts."),
+            },
+        }
+    }
+}
+
+impl Error for AocCliError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocCliError::Unauthorized(page)
+            | AocCliError::NotUnlocked(page)
+            | AocCliError::NotFound(page)
+            | AocCliError::RateLimited(page)
+            | AocCliError::Status(page)
+            | AocCliError::UnexpectedPage(page) => Some(page),
+            AocCliError::Network { cause, .. } => Some(cause.as_ref()),
+            AocCliError::Io { cause, .. } => Some(cause),
+            AocCliError::SessionNotFound | AocCliError::PartNotFound(_) => None,
+        }
+    }
+}
+
+/// What the website answered to a request that failed
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ErrorPage {
+    pub url: String,
+    pub status: u16,
+    /// How long the website asked to wait before trying again
+    pub retry_after: Option<Duration>,
+    /// The start of the page
+    pub body: String,
+}
+
+impl ErrorPage {
+    /// The error a page with this status stands for, judging by all of its body
+    fn error(url: &str, status: u16, body: &str, retry_after: Option<Duration>) -> AocCliError {
+        let page = ErrorPage {
+            url: url.to_string(),
+            status,
+            retry_after,
+            body: body.trim().chars().take(ERROR_PAGE_LENGTH).collect(),
+        };
+        let body = body.to_lowercase();
+        // Any page seen logged out links to the login, so that only tells of a refused session
+        // where the page meant for the user was expected
+        match status {
+            401 | 403 => AocCliError::Unauthorized(page),
+            200..=400 if body.contains("log in") => AocCliError::Unauthorized(page),
+            404 if body.contains("before it unlocks") => AocCliError::NotUnlocked(page),
+            404 => AocCliError::NotFound(page),
+            429 => AocCliError::RateLimited(page),
+            200..=399 => AocCliError::UnexpectedPage(page),
+            _ => AocCliError::Status(page),
+        }
+    }
+
+    fn from_response(url: &str, status: u16, response: ureq::Response) -> AocCliError {
+        let retry_after = response
+            .header("Retry-After")
+            .and_then(|seconds| seconds.parse().ok())
+            .map(Duration::from_secs);
+        let body = response.into_string().unwrap_or_default();
+        ErrorPage::error(url, status, &body, retry_after)
+    }
+}
+
+impl Display for ErrorPage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "status {}", self.status)?;
+        match self.body.lines().next() {
+            Some(line) => write!(f, ": {line}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Error for ErrorPage {}
+
+/// How requests are spaced out and retried
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Policy {
+    /// The least time between the start of two requests
+    pub min_interval: Duration,
+    /// How often a request that failed for a transient reason is tried again
+    pub retries: u32,
+    /// How long to wait before the first retry, doubling for every other one,
+    /// unless the website asks for longer
+    pub backoff: Duration,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy {
+            min_interval: Duration::from_secs(1),
+            retries: 3,
+            backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+    policy: Policy,
+    last_request: Cell<Option<Instant>>,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            policy: Policy::default(),
+            last_request: Cell::new(None),
+        }
+    }
+
+    pub fn with_policy(self, policy: Policy) -> Client {
+        Client { policy, ..self }
+    }
+
+    /// Uses `AOC_BASE_URL` if set and the session cookie wherever it is found
+    pub fn from_env() -> Result<Client, AocCliError> {
+        let base_url = env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        Ok(Client::new(&base_url, &session()?))
+    }
+
+    /// Refuses pages that look like an error page rather than an input
+    pub fn input(&self, year: u16, day: u8) -> Result<String, AocCliError> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let input = self.send(&url, true, || self.get(&url))?;
+        let start = input.trim_start().to_lowercase();
+        if input.trim().is_empty() || start.starts_with("<!doctype") || start.starts_with("<html") {
+            return Err(ErrorPage::error(&url, 200, &input, None));
+        }
+        Ok(input)
+    }
+
+    /// The description of every part unlocked so far, as markdown
+    pub fn puzzle(&self, year: u16, day: u8) -> Result<String, AocCliError> {
+        let url = format!("{}/{year}/day/{day}", self.base_url);
+        let html = self.send(&url, true, || self.get(&url))?;
+        if !html.contains("<main>") {
+            return Err(ErrorPage::error(&url, 200, &html, None));
+        }
+        Ok(puzzle_markdown(&html))
+    }
+
+    /// What the website said about the answer, and its message as markdown.
+    /// Never retried, so that an answer isn't submitted twice.
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<(Feedback, String), AocCliError> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        let html = self.send(&url, false, || {
+            self.agent
+                .post(&url)
+                .set("Cookie", &format!("session={}", self.session))
+                .send_form(&[("level", &part.to_string()), ("answer", answer)])
+                .map_err(Box::new)
+        })?;
+        let feedback = feedback(&html).ok_or_else(|| ErrorPage::error(&url, 200, &html, None))?;
+        Ok((feedback, puzzle_markdown(&html)))
+    }
+
+    fn get(&self, url: &str) -> Result<ureq::Response, Box<ureq::Error>> {
+        self.agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(Box::new)
+    }
+
+    /// Spaces out requests as the policy says, and retries them if `retry` is set
+    fn send(
+        &self,
+        url: &str,
+        retry: bool,
+        request: impl Fn() -> Result<ureq::Response, Box<ureq::Error>>,
+    ) -> Result<String, AocCliError> {
+        let network = |cause: Box<dyn Error + Send + Sync>| AocCliError::Network {
+            url: url.to_string(),
+            cause,
+        };
+        let mut attempt = 0;
+        loop {
+            if let Some(last) = self.last_request.get() {
+                thread::sleep(self.policy.min_interval.saturating_sub(last.elapsed()));
+            }
+            self.last_request.set(Some(Instant::now()));
+
+            let error = match request().map_err(|e| *e) {
+                Ok(response) => return response.into_string().map_err(|e| network(e.into())),
+                Err(ureq::Error::Status(status, response)) => {
+                    ErrorPage::from_response(url, status, response)
+                }
+                Err(ureq::Error::Transport(cause)) => network(cause.into()),
+            };
+            if !retry || attempt >= self.policy.retries || !error.is_transient() {
+                return Err(error);
+            }
+            let backoff = self.policy.backoff * 2u32.pow(attempt);
+            let delay = match &error {
+                AocCliError::RateLimited(page) => page.retry_after.unwrap_or(backoff).max(backoff),
+                _ => backoff,
+            };
+            eprintln!("{error} Trying again in {delay:?}...");
+            thread::sleep(delay);
+            attempt += 1;
+        }
+    }
+}
+
+fn session() -> Result<String, AocCliError> {
+    if let Some(session) = env::var(SESSION_VAR).ok().filter(|s| !s.trim().is_empty()) {
+        return Ok(session);
+    }
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".config")));
+    let candidates = [
+        home.map(|home| home.join(SESSION_FILE)),
+        config.map(|config| config.join(SESSION_FILE.trim_start_matches('.'))),
+    ];
+    for path in candidates.into_iter().flatten() {
+        if let Ok(session) = fs::read_to_string(&path) {
+            println!("Loaded session cookie from \"{}\".", path.display());
+            return Ok(session);
+        }
+    }
+    Err(AocCliError::SessionNotFound)
+}
+
+/// Only the `<main>` of the page describes the puzzle
+pub fn puzzle_markdown(html: &str) -> String {
+    let main = html
+        .split_once("<main>")
+        .and_then(|(_, rest)| rest.rsplit_once("</main>"))
+        .map_or(html, |(main, _)| main);
+    html2md::parse_html(main)
+}
+
+fn feedback(html: &str) -> Option<Feedback> {
+    if html.contains("That's the right answer") {
+        Some(Feedback::Correct)
+    } else if html.contains("That's not the right answer") {
+        if html.contains("your answer is too high") {
+            Some(Feedback::TooHigh)
+        } else if html.contains("your answer is too low") {
+            Some(Feedback::TooLow)
+        } else {
+            Some(Feedback::Wrong)
+        }
+    } else if html.contains("You gave an answer too recently") {
+        Some(Feedback::RateLimited)
+    } else if html.contains("You don't seem to be solving the right level") {
+        Some(Feedback::WrongLevel)
+    } else {
+        None
+    }
+}
+
+/// Renders the description in `src/puzzles`, fetching it only if it hasn't been downloaded
+pub fn read(day: u8, year: Option<u16>, part: Option<u8>) -> Result<(), AocCliError> {
+    let year = year.unwrap_or(YEAR);
+    let markdown = match fs::read_to_string(manifest_path(get_puzzle_path(day, year))) {
+        Ok(markdown) => markdown,
+        Err(_) => {
+            let client = Client::from_env()?;
+            println!("Fetching puzzle for day {day}, {year}...");
+            client.puzzle(year, day)?
+        }
+    };
+    let section = puzzle::section(&markdown, part).ok_or(AocCliError::PartNotFound(part))?;
+    print!("{}", puzzle::render(&section, puzzle::terminal_width()));
+    Ok(())
+}
+
+/// Keeps an input that was downloaded before, as inputs never change, and a description
+/// with both parts over one from before the second part was unlocked
+pub fn download(day: u8, year: Option<u16>) -> Result<(), AocCliError> {
+    let client = Client::from_env()?;
+    let year = year.unwrap_or(YEAR);
+    let input_path = get_input_path(day, year);
+    let puzzle_path = get_puzzle_path(day, year);
+
+    println!("Fetching puzzle for day {day}, {year}...");
+    let puzzle = client.puzzle(year, day)?;
+    let saved = fs::read_to_string(manifest_path(&puzzle_path)).unwrap_or_default();
+    let has_part_two = |markdown: &str| puzzle::section(markdown, Some(2)).is_some();
+    let described = !has_part_two(&saved) || has_part_two(&puzzle);
+    if described {
+        println!("Saving puzzle description to \"{puzzle_path}\"...");
+        write(&puzzle_path, &puzzle)?;
+    } else {
+        println!("Keeping the description in \"{puzzle_path}\", which has both parts.");
+    }
+
+    let existing = fs::read_to_string(manifest_path(&input_path)).unwrap_or_default();
+    let downloaded = existing.trim().is_empty();
+    if downloaded {
+        println!("Downloading input for day {day}, {year}...");
+        let input = client.input(year, day)?;
+        println!("Saving puzzle input to \"{input_path}\"...");
+        write(&input_path, &input)?;
+    } else {
+        println!("Keeping the input in \"{input_path}\", delete it to download it again.");
+    }
+
+    println!("Done!");
+    println!("---");
+    if downloaded {
+        println!("🎄 Successfully wrote input to \"{input_path}\".");
+    }
+    if described {
+        println!("🎄 Successfully wrote puzzle to \"{puzzle_path}\".");
+    }
+    Ok(())
+}
+
+/// Writes to a temporary file first, so that a failed write leaves the old contents intact
+fn write(path: &str, contents: &str) -> Result<(), AocCliError> {
+    let path = manifest_path(path);
+    let temporary = path.with_extension("download");
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(AocCliError::io(parent))?;
+    }
+    fs::write(&temporary, contents).map_err(AocCliError::io(&temporary))?;
+    fs::rename(&temporary, &path).map_err(AocCliError::io(&path))
+}
+
+/// Inputs and descriptions of other years are kept apart from this year's, like `src/inputs/2015/01.txt`
+fn get_input_path(day: u8, year: u16) -> String {
+    let day_padded = format!("{day:02}");
+    match year {
+        YEAR => format!("src/inputs/{day_padded}.txt"),
+        _ => format!("src/inputs/{year}/{day_padded}.txt"),
+    }
+}
+
+fn get_puzzle_path(day: u8, year: u16) -> String {
+    let day_padded = format!("{day:02}");
+    match year {
+        YEAR => format!("src/puzzles/{day_padded}.md"),
+        _ => format!("src/puzzles/{year}/{day_padded}.md"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Never waits long, so that tests don't
+    const FAST: Policy = Policy {
+        min_interval: Duration::ZERO,
+        retries: 2,
+        backoff: Duration::from_millis(1),
+    };
+
+    /// Answers the requests with the responses in order, and returns the requests
+    fn serve(
+        responses: &'static [(u16, &'static str)],
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let mut requests = vec![];
+            for ((status, body), stream) in responses.iter().zip(listener.incoming()) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(&stream);
+                let mut head: Vec<String> = (&mut reader)
+                    .lines()
+                    .map(Result::unwrap)
+                    .take_while(|line| !line.is_empty())
+                    .collect();
+                let length = head
+                    .iter()
+                    .find_map(|line| line.strip_prefix("Content-Length: "))
+                    .map_or(0, |length| length.parse().unwrap());
+                let mut request_body = vec![0; length];
+                reader.read_exact(&mut request_body).unwrap();
+                head.push(String::from_utf8(request_body).unwrap());
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+                requests.push(head.join("\n"));
+            }
+            requests
+        });
+        (base_url, server)
+    }
+
+    #[test]
+    fn test_client() {
+        let (base_url, server) = serve(&[
+            (200, "R2, L3\n"),
+            (
+                200,
+                "<html><main><article><h2>--- Day 1: No Time for a Taxicab ---</h2>\
+                 <p>Santa's sleigh uses a <em>very</em> high-precision clock.</p></article></main></html>",
+            ),
+        ]);
+        let client = Client::new(&base_url, "abc\n").with_policy(FAST);
+        assert_eq!(client.input(2016, 1).unwrap(), "R2, L3\n");
+        assert_eq!(
+            client.puzzle(2016, 1).unwrap(),
+            "\\--- Day 1: No Time for a Taxicab ---\n----------\n\n\
+             Santa's sleigh uses a *very* high-precision clock."
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2016/day/1/input HTTP/1.1"));
+        assert!(requests[0].contains("Cookie: session=abc"));
+        assert!(requests[1].starts_with("GET /2016/day/1 HTTP/1.1"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let (base_url, server) = serve(&[
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            ),
+            (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!",
+            ),
+            (404, "404 Not Found"),
+            (200, "<!DOCTYPE html>\n<html lang=\"en-us\">"),
+        ]);
+        let client = Client::new(&base_url, "expired").with_policy(FAST);
+        let error = client.input(2016, 1).unwrap_err();
+        assert!(matches!(error, AocCliError::Unauthorized(_)));
+        assert_eq!(
+            error.source().unwrap().to_string(),
+            "status 400: Puzzle inputs differ by user.  Please log in to get your puzzle input."
+        );
+        assert!(matches!(
+            client.input(2016, 1),
+            Err(AocCliError::NotUnlocked(_))
+        ));
+        assert!(matches!(
+            client.puzzle(2016, 26),
+            Err(AocCliError::NotFound(_))
+        ));
+        assert!(matches!(
+            client.input(2016, 1),
+            Err(AocCliError::UnexpectedPage(_))
+        ));
+        assert_eq!(server.join().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_retries() {
+        let (base_url, server) = serve(&[
+            (503, "Service Unavailable"),
+            (429, "Too Many Requests"),
+            (200, "1\n"),
+            (502, "Bad Gateway"),
+            (502, "Bad Gateway"),
+            (502, "Bad Gateway"),
+        ]);
+        let client = Client::new(&base_url, "abc").with_policy(FAST);
+        assert_eq!(client.input(2016, 1).unwrap(), "1\n");
+        let error = client.input(2016, 1).unwrap_err();
+        assert!(matches!(&error, AocCliError::Status(page) if page.status == 502));
+        assert!(error.is_transient());
+        assert_eq!(server.join().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn test_rate_limited_page() {
+        const PAGE: &str = "<html><header><a href=\"/auth/login\">[Log In]</a></header>\
+                    <main>Too many requests.</main></html>";
+        let url = "https://adventofcode.com/2016/day/1/input";
+        assert!(matches!(
+            ErrorPage::error(url, 429, PAGE, None),
+            AocCliError::RateLimited(_)
+        ));
+        assert!(matches!(
+            ErrorPage::error(url, 503, PAGE, None),
+            AocCliError::Status(_)
+        ));
+
+        let (base_url, server) = serve(&[(429, PAGE), (200, "1\n")]);
+        let client = Client::new(&base_url, "abc").with_policy(FAST);
+        assert_eq!(client.input(2016, 1).unwrap(), "1\n");
+        assert_eq!(server.join().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_paths() {
+        assert_eq!(get_input_path(5, YEAR), "src/inputs/05.txt");
+        assert_eq!(get_input_path(5, 2015), "src/inputs/2015/05.txt");
+        assert_eq!(get_puzzle_path(5, YEAR), "src/puzzles/05.md");
+        assert_eq!(get_puzzle_path(5, 2015), "src/puzzles/2015/05.md");
+    }
+
+    #[test]
+    fn test_min_interval() {
+        let (base_url, server) = serve(&[(200, "1\n"), (200, "2\n")]);
+        let policy = Policy {
+            min_interval: Duration::from_millis(100),
+            ..FAST
+        };
+        let client = Client::new(&base_url, "abc").with_policy(policy);
+        let start = Instant::now();
+        client.input(2016, 1).unwrap();
+        client.input(2016, 2).unwrap();
+        assert!(start.elapsed() >= policy.min_interval);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_submit() {
+        let (base_url, server) = serve(&[(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too high. \
+             Please wait one minute before trying again.</p></article></main>",
+        )]);
+        let client = Client::new(&base_url, "abc").with_policy(FAST);
+        let (feedback, message) = client.submit(2016, 1, 2, "257").unwrap();
+        assert_eq!(feedback, Feedback::TooHigh);
+        assert!(message.starts_with("That's not the right answer"));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2016/day/1/answer HTTP/1.1"));
+        assert!(requests[0].ends_with("\nlevel=2&answer=257"));
+    }
+
+    #[test]
+    fn test_feedback() {
+        assert_eq!(
+            feedback("<p>That's the right answer! You are one gold star closer.</p>"),
+            Some(Feedback::Correct)
+        );
+        assert_eq!(
+            feedback("<p>That's not the right answer. If you're stuck...</p>"),
+            Some(Feedback::Wrong)
+        );
+        assert_eq!(
+            feedback("<p>That's not the right answer; your answer is too low.</p>"),
+            Some(Feedback::TooLow)
+        );
+        assert_eq!(
+            feedback("<p>You gave an answer too recently. You have 38s left to wait.</p>"),
+            Some(Feedback::RateLimited)
+        );
+        assert_eq!(
+            feedback("<p>You don't seem to be solving the right level.</p>"),
+            Some(Feedback::WrongLevel)
+        );
+        assert_eq!(feedback("<p>Log in</p>"), None);
+    }
+
+    #[test]
+    fn test_puzzle_markdown() {
+        assert_eq!(
+            puzzle_markdown("<nav>2016</nav><main><p>Hi <code>x</code></p></main><footer/>"),
+            "Hi `x`"
+        );
+    }
+}
@@ -0,0 +1,1101 @@
+/*
+ * Assembunny, the language spoken by the EBHQ computers in days 12, 23 and 25.
+ * Example import from this file: `use advent_of_code::assembunny::{instructions, Computer};`.
+ */
+use crate::assembunny::Argument::{Register, Value};
+use crate::assembunny::Instruction::*;
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+pub mod analysis;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Argument {
+    Value(i32),
+    Register(char),
+}
+
+impl Display for Argument {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value(value) => write!(f, "{value}"),
+            Register(register) => write!(f, "{register}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Instruction {
+    Copy(Argument, Argument),
+    Increment(Argument),
+    Decrement(Argument),
+    JumpNotZero(Argument, Argument),
+    Toggle(Argument),
+    Out(Argument),
+    /// `x += y; y = 0`, fused by [`optimize`]
+    Add(Argument, Argument),
+    /// `x += y * z; t = 0; z = 0`, fused by [`optimize`] as `Mul(x, y, t, z)`
+    Mul(Argument, Argument, Argument, Argument),
+    /// `q += n / d; r = d - n % d; n = 0`, fused by [`optimize`] as `DivMod(n, d, q, r)`
+    DivMod(Argument, Argument, Argument, Argument),
+}
+
+impl Instruction {
+    /// - One-argument instructions: `inc` becomes `dec`, all others become `inc`.
+    /// - Two-argument instructions: `jnz` becomes `cpy`, all others become `jnz`.
+    pub fn toggle(self) -> Instruction {
+        match self {
+            Copy(x, y) => JumpNotZero(x, y),
+            JumpNotZero(x, y) => Copy(x, y),
+            Increment(x) => Decrement(x),
+            Decrement(x) | Toggle(x) | Out(x) => Increment(x),
+            instruction => instruction,
+        }
+    }
+
+    /// How many instructions this stands for, only fused loops cover more than one
+    pub fn length(&self) -> usize {
+        match self {
+            Add(..) => 3,
+            Mul(..) => 6,
+            DivMod(..) => 8,
+            _ => 1,
+        }
+    }
+}
+
+/// Canonical assembunny, which [`instructions`] parses back into the same instruction.
+/// Fused loops print as `add x y`, `mul x y t z` and `divmod n d q r`.
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Copy(x, y) => write!(f, "cpy {x} {y}"),
+            Increment(x) => write!(f, "inc {x}"),
+            Decrement(x) => write!(f, "dec {x}"),
+            JumpNotZero(x, y) => write!(f, "jnz {x} {y}"),
+            Toggle(x) => write!(f, "tgl {x}"),
+            Out(x) => write!(f, "out {x}"),
+            Add(x, y) => write!(f, "add {x} {y}"),
+            Mul(x, y, t, z) => write!(f, "mul {x} {y} {t} {z}"),
+            DivMod(n, d, q, r) => write!(f, "divmod {n} {d} {q} {r}"),
+        }
+    }
+}
+
+/// The program as text, one instruction per line
+pub fn print(instructions: &[Instruction]) -> String {
+    instructions.iter().join("\n")
+}
+
+/// Receives every value transmitted by `out`
+pub trait Output {
+    /// Returning `false` halts the program
+    fn transmit(&mut self, value: i32) -> bool;
+}
+
+impl<F: FnMut(i32) -> bool> Output for F {
+    fn transmit(&mut self, value: i32) -> bool {
+        self(value)
+    }
+}
+
+/// The outcome of [`Computer::step`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Step {
+    /// The instruction, or fused loop, that just ran
+    Ran(Instruction),
+    /// `out` ran and the output asked to halt
+    Interrupted(Instruction),
+    /// The instruction pointer is outside of the program
+    Halted,
+}
+
+/// The outcome of [`Computer::clock_signal`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Signal {
+    /// The output alternates `0, 1, 0, 1, ..` forever, repeating every `period` values
+    Clock { period: usize },
+    /// The output broke the pattern, or the program halted
+    NotAClock,
+    /// Neither could be proven within the budget
+    Undetermined,
+}
+
+#[derive(Debug, Clone)]
+pub struct Computer {
+    instructions: Vec<Instruction>,
+    /// Fused loops from [`optimize`], indexed by the first instruction they replace
+    fused: Vec<Option<Instruction>>,
+    /// What actually runs, see [`compile`]
+    ops: Vec<Op>,
+    registers: [i32; 4],
+    ip: isize,
+    optimized: bool,
+}
+
+impl Computer {
+    pub fn new(instructions: Vec<Instruction>) -> Self {
+        let ops = compile(&instructions, &[]);
+        Self {
+            instructions,
+            fused: vec![],
+            ops,
+            registers: [0; 4],
+            ip: 0,
+            optimized: false,
+        }
+    }
+
+    /// Runs the loops recognized by [`optimize`] in a single step.
+    /// They are recognized again every time `tgl` modifies the program.
+    pub fn optimized(instructions: Vec<Instruction>) -> Self {
+        let fused = optimize(&instructions);
+        let ops = compile(&instructions, &fused);
+        Self {
+            instructions,
+            fused,
+            ops,
+            registers: [0; 4],
+            ip: 0,
+            optimized: true,
+        }
+    }
+
+    pub fn get(&self, register: char) -> i32 {
+        self.registers[slot(register)]
+    }
+
+    pub fn set(&mut self, register: char, value: i32) {
+        self.registers[slot(register)] = value
+    }
+
+    /// Registers `a` through `d`
+    pub fn registers(&self) -> [i32; 4] {
+        self.registers
+    }
+
+    /// Index of the next instruction to run
+    pub fn ip(&self) -> isize {
+        self.ip
+    }
+
+    /// The program as it currently is, including anything modified by `tgl`
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// Runs until the instruction pointer leaves the program, ignoring any output
+    pub fn run(&mut self) {
+        self.run_with(&mut |_| true)
+    }
+
+    /// Runs until the instruction pointer leaves the program or `output` asks to halt
+    pub fn run_with(&mut self, output: &mut impl Output) {
+        loop {
+            match self.run_registers_only() {
+                None => return,
+                Some(op) => {
+                    let idx = self.ip as usize;
+                    if let Executed::Interrupted = self.execute_op(op, idx, output) {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Runs ops until one needs more than the registers and instruction pointer, returning it.
+    /// Keeping those local lets them live in CPU registers, which is where most time is spent.
+    /// Jumps within the program and the [`Op::Halt`] at its end keep `ip` a valid index.
+    #[inline]
+    fn run_registers_only(&mut self) -> Option<Op> {
+        let ops = &self.ops;
+        let mut registers = self.registers;
+        let mut ip = self.current()?;
+        let pending = loop {
+            let op = ops[ip];
+            match op {
+                Op::Load(value, x) => registers[x as usize] = value,
+                Op::Move(y, x) => registers[x as usize] = registers[y as usize],
+                Op::Increment(x) => registers[x as usize] += 1,
+                Op::Decrement(x) => registers[x as usize] -= 1,
+                Op::Jump(target) => {
+                    ip = target;
+                    continue;
+                }
+                Op::JumpIf(x, target) if registers[x as usize] != 0 => {
+                    ip = target;
+                    continue;
+                }
+                Op::JumpIf(..) | Op::Skip => {}
+                Op::Add(..) | Op::Mul(..) | Op::DivMod(..) => {
+                    match arithmetic(op, &mut registers) {
+                        Some(length) => {
+                            ip += length;
+                            continue;
+                        }
+                        None => break Some(op),
+                    }
+                }
+                Op::Halt => break None,
+                op => break Some(op),
+            }
+            ip += 1;
+        };
+        self.registers = registers;
+        self.ip = ip as isize;
+        pending
+    }
+
+    /// Runs until one of the `breakpoints` is hit, or the program halts or is interrupted.
+    /// At least one step is taken, so breakpoints on the current instruction are passed over.
+    pub fn run_until(&mut self, breakpoints: &[Breakpoint], output: &mut impl Output) -> Step {
+        loop {
+            let step = self.step(output);
+            if !matches!(step, Step::Ran(_)) || breakpoints.iter().any(|b| b.is_hit(self)) {
+                return step;
+            }
+        }
+    }
+
+    /// Runs the instruction at the instruction pointer, or the whole loop when it has been fused
+    pub fn step(&mut self, output: &mut impl Output) -> Step {
+        let Some(idx) = self.current() else {
+            return Step::Halted;
+        };
+        // `tgl` can modify the very instruction that is running, so look it up beforehand
+        let instruction = self.instructions[idx];
+        let fused = self.fused.get(idx).copied().flatten();
+        match self.execute(output) {
+            Some(Executed::Instruction) => Step::Ran(instruction),
+            Some(Executed::Fused) => Step::Ran(fused.unwrap()),
+            Some(Executed::Interrupted) => Step::Interrupted(instruction),
+            None => Step::Halted,
+        }
+    }
+
+    /// Runs up to `max_steps`, stopping early once `max_output` values have been transmitted
+    pub fn trace(&mut self, max_steps: usize, max_output: usize) -> Trace {
+        let mut trace = Trace::default();
+        while trace.steps.len() < max_steps {
+            let ip = self.ip;
+            let output = &mut trace.output;
+            let step = self.step(&mut |value| {
+                output.push(value);
+                output.len() < max_output
+            });
+            match step {
+                Step::Ran(instruction) | Step::Interrupted(instruction) => {
+                    trace.steps.push(TraceStep {
+                        ip,
+                        instruction,
+                        registers: self.registers,
+                    })
+                }
+                Step::Halted => {}
+            }
+            if !matches!(step, Step::Ran(_)) {
+                trace.halted = step == Step::Halted;
+                break;
+            }
+        }
+        trace
+    }
+
+    /// Runs up to `max_steps` to prove the output is a clock signal.
+    /// Every `out` snapshots the instruction pointer, registers and program: once a snapshot
+    /// repeats, everything transmitted since will be transmitted again, forever.
+    pub fn clock_signal(&mut self, max_steps: usize) -> Signal {
+        let mut snapshots = HashMap::new();
+        let mut transmitted = 0;
+        for _ in 0..max_steps {
+            let mut value = None;
+            if self.step(&mut |out| value.replace(out).is_none()) == Step::Halted {
+                return Signal::NotAClock;
+            }
+            let Some(value) = value else {
+                continue;
+            };
+            if value != (transmitted % 2) as i32 {
+                return Signal::NotAClock;
+            }
+            transmitted += 1;
+            let snapshot = (self.ip, self.registers, self.instructions.clone());
+            if let Some(previous) = snapshots.insert(snapshot, transmitted) {
+                let period = transmitted - previous;
+                // An odd period repeats a 0 where a 1 is expected
+                return match period % 2 {
+                    0 => Signal::Clock { period },
+                    _ => Signal::NotAClock,
+                };
+            }
+        }
+        Signal::Undetermined
+    }
+
+    fn current(&self) -> Option<usize> {
+        usize::try_from(self.ip)
+            .ok()
+            .filter(|idx| *idx < self.instructions.len())
+    }
+
+    /// Runs the op at the instruction pointer, `None` when it is outside of the program
+    #[inline]
+    fn execute(&mut self, output: &mut impl Output) -> Option<Executed> {
+        let idx = self.current()?;
+        Some(self.execute_op(self.ops[idx], idx, output))
+    }
+
+    #[inline]
+    fn execute_op(&mut self, op: Op, idx: usize, output: &mut impl Output) -> Executed {
+        let registers = &mut self.registers;
+        match op {
+            Op::Load(value, x) => registers[x as usize] = value,
+            Op::Move(y, x) => registers[x as usize] = registers[y as usize],
+            Op::Increment(x) => registers[x as usize] += 1,
+            Op::Decrement(x) => registers[x as usize] -= 1,
+            Op::Jump(target) => {
+                self.ip = target as isize;
+                return Executed::Instruction;
+            }
+            Op::JumpIf(x, target) if registers[x as usize] != 0 => {
+                self.ip = target as isize;
+                return Executed::Instruction;
+            }
+            Op::JumpOut(x, target) if operand(registers, x) != 0 => {
+                self.ip = target;
+                return Executed::Instruction;
+            }
+            Op::JumpBy(x, offset) if operand(registers, x) != 0 => {
+                self.ip += registers[offset as usize] as isize;
+                return Executed::Instruction;
+            }
+            Op::Toggle(x) => {
+                let target = self.ip + operand(registers, x) as isize;
+                self.toggle(target)
+            }
+            Op::Out(x) if !output.transmit(operand(registers, x)) => {
+                self.ip += 1;
+                return Executed::Interrupted;
+            }
+            Op::Add(..) | Op::Mul(..) | Op::DivMod(..) => {
+                return match arithmetic(op, registers) {
+                    Some(length) => {
+                        self.ip += length as isize;
+                        Executed::Fused
+                    }
+                    // The fused loop can't handle these registers, run the original instructions instead
+                    None => self.execute_unfused(idx, output),
+                };
+            }
+            _ => {}
+        }
+        self.ip += 1;
+        Executed::Instruction
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn execute_unfused(&mut self, idx: usize, output: &mut impl Output) -> Executed {
+        let op = lower(idx, self.instructions[idx], self.instructions.len());
+        self.execute_op(op, idx, output)
+    }
+
+    /// Patches the instruction at `target` and recompiles whatever it affects
+    fn toggle(&mut self, target: isize) {
+        let Some(idx) = usize::try_from(target)
+            .ok()
+            .filter(|idx| *idx < self.instructions.len())
+        else {
+            return;
+        };
+        self.instructions[idx] = self.instructions[idx].toggle();
+        if self.optimized {
+            self.fused = optimize(&self.instructions);
+            self.ops = compile(&self.instructions, &self.fused);
+        } else {
+            self.ops[idx] = lower(idx, self.instructions[idx], self.instructions.len());
+        }
+    }
+}
+
+enum Executed {
+    Instruction,
+    Fused,
+    Interrupted,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Breakpoint {
+    /// Stops before the instruction at this index runs
+    Address(usize),
+    /// Stops once the register compares to the value
+    Register(char, Comparison, i32),
+}
+
+impl Breakpoint {
+    pub fn is_hit(&self, computer: &Computer) -> bool {
+        match *self {
+            Breakpoint::Address(address) => computer.ip == address as isize,
+            Breakpoint::Register(register, comparison, value) => {
+                let current = computer.get(register);
+                match comparison {
+                    Comparison::Equal => current == value,
+                    Comparison::NotEqual => current != value,
+                    Comparison::Less => current < value,
+                    Comparison::LessOrEqual => current <= value,
+                    Comparison::Greater => current > value,
+                    Comparison::GreaterOrEqual => current >= value,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TraceStep {
+    pub ip: isize,
+    pub instruction: Instruction,
+    /// Registers `a` through `d` after the instruction ran
+    pub registers: [i32; 4],
+}
+
+impl Display for TraceStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let [a, b, c, d] = self.registers;
+        write!(
+            f,
+            "{:>4}  {:<20}  a={a} b={b} c={c} d={d}",
+            self.ip,
+            self.instruction.to_string()
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Trace {
+    pub steps: Vec<TraceStep>,
+    /// Every value transmitted by `out`
+    pub output: Vec<i32>,
+    /// Whether the program ran to completion within the limits
+    pub halted: bool,
+}
+
+impl Display for Trace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for step in &self.steps {
+            writeln!(f, "{step}")?;
+        }
+        write!(f, "output: {}", self.output.iter().join(","))
+    }
+}
+
+pub(crate) fn slot(register: char) -> usize {
+    match register {
+        'a'..='d' => register as usize - 'a' as usize,
+        _ => panic!("invalid register {register}"),
+    }
+}
+
+/// An [`Instruction`] lowered for [`Computer`] to run,
+/// with registers resolved to slots and jumps resolved to absolute targets
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Op {
+    Load(i32, u8),
+    Move(u8, u8),
+    Increment(u8),
+    Decrement(u8),
+    /// Jumps to an instruction of the program
+    Jump(usize),
+    JumpIf(u8, usize),
+    /// `jnz` to outside of the program, which halts it
+    JumpOut(Operand, isize),
+    /// `jnz` by an offset read from a register
+    JumpBy(Operand, u8),
+    Toggle(Operand),
+    Out(Operand),
+    Add(u8, u8),
+    Mul(u8, Operand, u8, u8),
+    DivMod(u8, i32, u8, u8),
+    /// Invalid instructions, or jumps that can never be taken
+    Skip,
+    /// Follows the last instruction, so that running off the end needs no bounds check
+    Halt,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Operand {
+    Value(i32),
+    Slot(u8),
+}
+
+fn operand(registers: &[i32; 4], operand: Operand) -> i32 {
+    match operand {
+        Operand::Value(value) => value,
+        Operand::Slot(x) => registers[x as usize],
+    }
+}
+
+/// Lowers every instruction, preferring the fused loop starting there if there is one,
+/// and ends the program with [`Op::Halt`]
+fn compile(instructions: &[Instruction], fused: &[Option<Instruction>]) -> Vec<Op> {
+    instructions
+        .iter()
+        .enumerate()
+        .map(|(idx, instruction)| {
+            fused
+                .get(idx)
+                .copied()
+                .flatten()
+                .and_then(lower_fused)
+                .unwrap_or_else(|| lower(idx, *instruction, instructions.len()))
+        })
+        .chain([Op::Halt])
+        .collect_vec()
+}
+
+/// Lowers the instruction at `idx` of a program `length` instructions long
+fn lower(idx: usize, instruction: Instruction, length: usize) -> Op {
+    let register = |register: char| slot(register) as u8;
+    let target = |offset: i32| idx as isize + offset as isize;
+    let inside = |offset: i32| {
+        usize::try_from(target(offset))
+            .ok()
+            .filter(|target| *target < length)
+    };
+    match instruction {
+        Copy(Value(value), Register(x)) => Op::Load(value, register(x)),
+        Copy(Register(y), Register(x)) => Op::Move(register(y), register(x)),
+        Increment(Register(x)) => Op::Increment(register(x)),
+        Decrement(Register(x)) => Op::Decrement(register(x)),
+        JumpNotZero(Value(0), _) => Op::Skip,
+        JumpNotZero(x, Value(offset)) if inside(offset).is_none() => {
+            Op::JumpOut(lower_argument(x), target(offset))
+        }
+        JumpNotZero(Value(_), Value(offset)) => Op::Jump(idx.wrapping_add_signed(offset as isize)),
+        JumpNotZero(Register(x), Value(offset)) => {
+            Op::JumpIf(register(x), idx.wrapping_add_signed(offset as isize))
+        }
+        JumpNotZero(x, Register(offset)) => Op::JumpBy(lower_argument(x), register(offset)),
+        Toggle(x) => Op::Toggle(lower_argument(x)),
+        Out(x) => Op::Out(lower_argument(x)),
+        _ => Op::Skip,
+    }
+}
+
+fn lower_fused(instruction: Instruction) -> Option<Op> {
+    let register = |register: char| slot(register) as u8;
+    match instruction {
+        Add(Register(x), Register(y)) => Some(Op::Add(register(x), register(y))),
+        Mul(Register(x), y, Register(t), Register(z)) => Some(Op::Mul(
+            register(x),
+            lower_argument(y),
+            register(t),
+            register(z),
+        )),
+        DivMod(Register(n), Value(d), Register(q), Register(r)) => {
+            Some(Op::DivMod(register(n), d, register(q), register(r)))
+        }
+        _ => None,
+    }
+}
+
+/// Runs a fused loop, giving how many instructions it spans.
+/// `None` if the registers are such that the loop has to run as it is written.
+#[inline]
+fn arithmetic(op: Op, registers: &mut [i32; 4]) -> Option<usize> {
+    match op {
+        Op::Add(x, y) if registers[y as usize] > 0 => {
+            registers[x as usize] += registers[y as usize];
+            registers[y as usize] = 0;
+            Some(3)
+        }
+        Op::Mul(x, y, t, z) if operand(registers, y) > 0 && registers[z as usize] > 0 => {
+            registers[x as usize] += operand(registers, y) * registers[z as usize];
+            registers[t as usize] = 0;
+            registers[z as usize] = 0;
+            Some(6)
+        }
+        Op::DivMod(n, d, q, r) if registers[n as usize] >= 0 => {
+            let dividend = registers[n as usize];
+            registers[q as usize] += dividend / d;
+            registers[r as usize] = d - dividend % d;
+            registers[n as usize] = 0;
+            Some(8)
+        }
+        _ => None,
+    }
+}
+
+fn lower_argument(argument: Argument) -> Operand {
+    match argument {
+        Value(value) => Operand::Value(value),
+        Register(register) => Operand::Slot(slot(register) as u8),
+    }
+}
+
+/// Peephole optimization, recognizing the loops assembunny uses for arithmetic.
+/// The instructions themselves are left untouched so jumps into the middle of a loop,
+/// or registers the fused version can't handle, still run the original code.
+///
+/// Addition, `x += y`:
+/// ```text
+/// inc x
+/// dec y
+/// jnz y -2
+/// ```
+///
+/// Multiplication via repeated addition, `x += y * z`:
+/// ```text
+/// cpy y t
+/// inc x
+/// dec t
+/// jnz t -2
+/// dec z
+/// jnz z -5
+/// ```
+///
+/// Division via repeated subtraction, `q += n / d` leaving `d - n % d` in `r`:
+/// ```text
+/// cpy d r
+/// jnz n 2
+/// jnz 1 6
+/// dec n
+/// dec r
+/// jnz r -4
+/// inc q
+/// jnz 1 -7
+/// ```
+pub fn optimize(instructions: &[Instruction]) -> Vec<Option<Instruction>> {
+    (0..instructions.len())
+        .map(|idx| {
+            let window = &instructions[idx..];
+            multiplication(window)
+                .or_else(|| division(window))
+                .or_else(|| addition(window))
+        })
+        .collect_vec()
+}
+
+fn addition(window: &[Instruction]) -> Option<Instruction> {
+    let [first, second, third, ..] = window else {
+        return None;
+    };
+    match (first, second, third) {
+        (
+            Increment(Register(x)),
+            Decrement(Register(y)),
+            JumpNotZero(Register(jump), Value(-2)),
+        )
+        | (
+            Decrement(Register(y)),
+            Increment(Register(x)),
+            JumpNotZero(Register(jump), Value(-2)),
+        ) if y == jump && x != y => Some(Add(Register(*x), Register(*y))),
+        _ => None,
+    }
+}
+
+fn multiplication(window: &[Instruction]) -> Option<Instruction> {
+    let [first, _, _, _, fifth, sixth, ..] = window else {
+        return None;
+    };
+    match (first, addition(&window[1..]), fifth, sixth) {
+        (
+            Copy(y, Register(t)),
+            Some(Add(Register(x), Register(counter))),
+            Decrement(Register(z)),
+            JumpNotZero(Register(jump), Value(-5)),
+        ) if counter == *t
+            && z == jump
+            && ![x, *t].contains(z)
+            && ![Register(x), Register(*t), Register(*z)].contains(y) =>
+        {
+            Some(Mul(Register(x), *y, Register(*t), Register(*z)))
+        }
+        _ => None,
+    }
+}
+
+fn division(window: &[Instruction]) -> Option<Instruction> {
+    let [first, second, third, fourth, fifth, sixth, seventh, eighth, ..] = window else {
+        return None;
+    };
+    match (first, second, third, fourth, fifth, sixth, seventh, eighth) {
+        (
+            Copy(Value(d), Register(r)),
+            JumpNotZero(Register(n), Value(2)),
+            JumpNotZero(Value(exit), Value(6)),
+            Decrement(Register(n2)),
+            Decrement(Register(r2)),
+            JumpNotZero(Register(r3), Value(-4)),
+            Increment(Register(q)),
+            JumpNotZero(Value(repeat), Value(-7)),
+        ) if *d > 0
+            && *exit != 0
+            && *repeat != 0
+            && n == n2
+            && r == r2
+            && r == r3
+            && ![n, r].contains(&q)
+            && n != r =>
+        {
+            Some(DivMod(Register(*n), Value(*d), Register(*q), Register(*r)))
+        }
+        _ => None,
+    }
+}
+
+pub use parse::instructions;
+
+mod parse {
+    use crate::assembunny::Argument::{Register, Value};
+    use crate::assembunny::Instruction::{
+        Add, Copy, Decrement, DivMod, Increment, JumpNotZero, Mul, Out, Toggle,
+    };
+    use crate::assembunny::{Argument, Instruction};
+    use nom::branch::alt;
+    use nom::bytes::complete::tag;
+    use nom::character::complete::{char as nom_char, i32 as nom_i32, line_ending, one_of};
+    use nom::combinator::map;
+    use nom::multi::separated_list1;
+    use nom::sequence::{preceded, separated_pair, tuple};
+    use nom::IResult;
+
+    pub fn instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
+        separated_list1(line_ending, instruction)(input)
+    }
+    fn instruction(input: &str) -> IResult<&str, Instruction> {
+        alt((
+            copy,
+            increment,
+            decrement,
+            jump_not_zero,
+            toggle,
+            out,
+            add,
+            mul,
+            div_mod,
+        ))(input)
+    }
+    fn copy(input: &str) -> IResult<&str, Instruction> {
+        map(preceded(tag("cpy "), two_args), |(x, y)| Copy(x, y))(input)
+    }
+    fn increment(input: &str) -> IResult<&str, Instruction> {
+        map(preceded(tag("inc "), argument), Increment)(input)
+    }
+    fn decrement(input: &str) -> IResult<&str, Instruction> {
+        map(preceded(tag("dec "), argument), Decrement)(input)
+    }
+    fn jump_not_zero(input: &str) -> IResult<&str, Instruction> {
+        map(preceded(tag("jnz "), two_args), |(x, y)| JumpNotZero(x, y))(input)
+    }
+    fn toggle(input: &str) -> IResult<&str, Instruction> {
+        map(preceded(tag("tgl "), argument), Toggle)(input)
+    }
+    fn out(input: &str) -> IResult<&str, Instruction> {
+        map(preceded(tag("out "), argument), Out)(input)
+    }
+    fn add(input: &str) -> IResult<&str, Instruction> {
+        map(preceded(tag("add "), two_args), |(x, y)| Add(x, y))(input)
+    }
+    fn mul(input: &str) -> IResult<&str, Instruction> {
+        map(preceded(tag("mul "), four_args), |(x, y, t, z)| {
+            Mul(x, y, t, z)
+        })(input)
+    }
+    fn div_mod(input: &str) -> IResult<&str, Instruction> {
+        map(preceded(tag("divmod "), four_args), |(n, d, q, r)| {
+            DivMod(n, d, q, r)
+        })(input)
+    }
+
+    fn two_args(input: &str) -> IResult<&str, (Argument, Argument)> {
+        separated_pair(argument, nom_char(' '), argument)(input)
+    }
+    fn four_args(input: &str) -> IResult<&str, (Argument, Argument, Argument, Argument)> {
+        tuple((
+            argument,
+            preceded(nom_char(' '), argument),
+            preceded(nom_char(' '), argument),
+            preceded(nom_char(' '), argument),
+        ))(input)
+    }
+    fn argument(input: &str) -> IResult<&str, Argument> {
+        alt((value, register))(input)
+    }
+    fn value(input: &str) -> IResult<&str, Argument> {
+        map(nom_i32, Value)(input)
+    }
+    fn register(input: &str) -> IResult<&str, Argument> {
+        map(one_of("abcd"), Register)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instructions() {
+        assert_eq!(
+            instructions("cpy 41 a\ninc a\ndec b\njnz c -2\ntgl d\nout a"),
+            Ok((
+                "",
+                vec![
+                    Copy(Value(41), Register('a')),
+                    Increment(Register('a')),
+                    Decrement(Register('b')),
+                    JumpNotZero(Register('c'), Value(-2)),
+                    Toggle(Register('d')),
+                    Out(Register('a')),
+                ]
+            ))
+        );
+    }
+
+    /// Fixed-seed xorshift, so the round trip is checked against the same programs every run
+    struct Random(u64);
+
+    impl Random {
+        fn below(&mut self, bound: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % bound
+        }
+
+        fn argument(&mut self) -> Argument {
+            match self.below(4) {
+                0 => Value([0, 1, -1, i32::MIN, i32::MAX][self.below(5) as usize]),
+                1 => Value(self.below(u32::MAX as u64) as i32),
+                _ => Register((b'a' + self.below(4) as u8) as char),
+            }
+        }
+
+        fn instruction(&mut self) -> Instruction {
+            match self.below(9) {
+                0 => Copy(self.argument(), self.argument()),
+                1 => Increment(self.argument()),
+                2 => Decrement(self.argument()),
+                3 => JumpNotZero(self.argument(), self.argument()),
+                4 => Toggle(self.argument()),
+                5 => Out(self.argument()),
+                6 => Add(self.argument(), self.argument()),
+                7 => Mul(
+                    self.argument(),
+                    self.argument(),
+                    self.argument(),
+                    self.argument(),
+                ),
+                _ => DivMod(
+                    self.argument(),
+                    self.argument(),
+                    self.argument(),
+                    self.argument(),
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_print() {
+        let program = "cpy 41 a\ninc a\ndec b\njnz c -2\ntgl d\nout a";
+        assert_eq!(print(&instructions(program).unwrap().1), program);
+        assert_eq!(
+            Mul(Register('d'), Value(12), Register('b'), Register('c')).to_string(),
+            "mul d 12 b c"
+        );
+        // `tgl` can create instructions that don't do anything, like copying into a value
+        assert_eq!(
+            JumpNotZero(Value(1), Value(2)).toggle().to_string(),
+            "cpy 1 2"
+        );
+    }
+
+    #[test]
+    fn test_print_round_trip() {
+        let mut random = Random(2016);
+        for _ in 0..1_000 {
+            let program = (0..=random.below(30))
+                .map(|_| random.instruction())
+                .collect_vec();
+            assert_eq!(instructions(&print(&program)), Ok(("", program)));
+        }
+
+        let mut computer = Computer::new(
+            instructions("cpy 2 a\ntgl a\ntgl a\ntgl a\ncpy 1 a\ndec a\ndec a")
+                .unwrap()
+                .1,
+        );
+        computer.run();
+        let toggled = computer.instructions().to_vec();
+        assert_eq!(instructions(&print(&toggled)), Ok(("", toggled)));
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut computer = Computer::new(instructions("tgl a\ninc b\ninc b").unwrap().1);
+        computer.set('a', 1);
+        computer.run();
+        assert_eq!(computer.get('b'), 0);
+    }
+
+    #[test]
+    fn test_clock_signal() {
+        let clock_signal = |program: &str| {
+            Computer::optimized(instructions(program).unwrap().1).clock_signal(1_000)
+        };
+        assert_eq!(
+            clock_signal("cpy 0 a\nout a\ninc a\nout a\ndec a\njnz 1 -4"),
+            Signal::Clock { period: 2 }
+        );
+        assert_eq!(clock_signal("out 0\nout 1\nout 1"), Signal::NotAClock);
+        assert_eq!(clock_signal("out 0\nout 1"), Signal::NotAClock);
+        // Alternates for 20 values before breaking the pattern
+        assert_eq!(
+            clock_signal("cpy 10 b\nout 0\nout 1\ndec b\njnz b -3\nout 0\nout 0"),
+            Signal::NotAClock
+        );
+        // Never repeats, as `a` keeps growing
+        assert_eq!(
+            clock_signal("out 0\nout 1\ninc a\njnz 1 -3"),
+            Signal::Undetermined
+        );
+    }
+
+    #[test]
+    fn test_output() {
+        let mut transmitted = vec![];
+        let mut computer = Computer::new(instructions("out a\ninc a\njnz 1 -2").unwrap().1);
+        computer.run_with(&mut |value| {
+            transmitted.push(value);
+            transmitted.len() < 3
+        });
+        assert_eq!(transmitted, vec![0, 1, 2]);
+    }
+
+    /// Runs `program` with and without optimization, expecting the same registers from both
+    fn run_both(program: &str, registers: &[(char, i32)]) -> [i32; 4] {
+        let instructions = instructions(program).unwrap().1;
+        let mut plain = Computer::new(instructions.clone());
+        let mut optimized = Computer::optimized(instructions);
+        for &(register, value) in registers {
+            plain.set(register, value);
+            optimized.set(register, value);
+        }
+        plain.run();
+        optimized.run();
+        assert_eq!(plain.registers, optimized.registers);
+        optimized.registers
+    }
+
+    #[test]
+    fn test_optimize() {
+        let program = "cpy a d\ncpy 11 c\ncpy 12 b\ninc d\ndec b\njnz b -2\ndec c\njnz c -5\n\
+            cpy 2 c\njnz d 2\njnz 1 6\ndec d\ndec c\njnz c -4\ninc a\njnz 1 -7";
+        let fused = optimize(&instructions(program).unwrap().1);
+        assert_eq!(
+            fused[2],
+            Some(Mul(Register('d'), Value(12), Register('b'), Register('c')))
+        );
+        assert_eq!(fused[3], Some(Add(Register('d'), Register('b'))));
+        assert_eq!(
+            fused[8],
+            Some(DivMod(
+                Register('d'),
+                Value(2),
+                Register('a'),
+                Register('c')
+            ))
+        );
+        assert_eq!(fused.iter().flatten().count(), 3);
+
+        assert_eq!(run_both(program, &[('a', 5)]), [5 + 68, 0, 1, 0]);
+        assert_eq!(run_both(program, &[('a', 6)]), [6 + 69, 0, 2, 0]);
+    }
+
+    #[test]
+    fn test_optimize_jump_into_loop() {
+        // Starting halfway through the loop runs the original instructions for that iteration
+        let registers = run_both("jnz 1 2\ninc a\ndec b\njnz b -2", &[('b', 3)]);
+        assert_eq!(registers, [2, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_optimize_after_toggle() {
+        // `tgl` turns `inc a` into `dec a`, which must no longer run as a fused addition
+        let registers = run_both("cpy 2 c\ntgl c\ncpy 3 b\ninc a\ndec b\njnz b -2", &[]);
+        assert_eq!(registers, [-3, 0, 2, 0]);
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut computer = Computer::new(instructions("cpy 2 a\ndec a\njnz a -1").unwrap().1);
+        let trace = computer.trace(100, 10);
+        assert!(trace.halted);
+        assert_eq!(
+            trace.steps.iter().map(|step| step.ip).collect::<Vec<_>>(),
+            vec![0, 1, 2, 1, 2]
+        );
+        assert_eq!(trace.steps[1].registers, [1, 0, 0, 0]);
+
+        let mut computer = Computer::new(instructions("out a\ninc a\njnz 1 -2").unwrap().1);
+        let trace = computer.trace(100, 2);
+        assert!(!trace.halted);
+        assert_eq!(trace.output, vec![0, 1]);
+        assert_eq!(trace.steps.len(), 4);
+        assert_eq!(computer.trace(2, 10).steps.len(), 2);
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let mut computer = Computer::new(instructions("inc a\ninc b\njnz 1 -2").unwrap().1);
+        let breakpoints = [
+            Breakpoint::Address(1),
+            Breakpoint::Register('b', Comparison::GreaterOrEqual, 3),
+        ];
+        let mut output = |_| true;
+        assert_eq!(
+            computer.run_until(&breakpoints, &mut output),
+            Step::Ran(Increment(Register('a')))
+        );
+        assert_eq!(computer.ip(), 1);
+        computer.run_until(&breakpoints, &mut output);
+        assert_eq!(computer.ip(), 1);
+        assert_eq!(computer.registers(), [2, 1, 0, 0]);
+        computer.run_until(&breakpoints[1..], &mut output);
+        assert_eq!(computer.registers(), [3, 3, 0, 0]);
+    }
+
+    #[test]
+    fn test_compile() {
+        let program = "cpy 1 a\ncpy a b\njnz 1 3\njnz 0 -1\njnz c -2\njnz 1 d\njnz a -7\njnz 1 2";
+        let instructions = instructions(program).unwrap().1;
+        assert_eq!(
+            compile(&instructions, &[]),
+            vec![
+                Op::Load(1, 0),
+                Op::Move(0, 1),
+                Op::Jump(5),
+                Op::Skip,
+                Op::JumpIf(2, 2),
+                Op::JumpBy(Operand::Value(1), 3),
+                Op::JumpOut(Operand::Slot(0), -1),
+                Op::JumpOut(Operand::Value(1), 9),
+                Op::Halt,
+            ]
+        );
+    }
+}
@@ -0,0 +1,552 @@
+/*
+ * Static analysis of assembunny programs: control flow, loops, symbolic evaluation and a decompiler.
+ * Example: `println!("{}", Analysis::new(&instructions).decompile());`
+ */
+use crate::assembunny::Argument::{Register, Value};
+use crate::assembunny::Instruction::*;
+use crate::assembunny::{optimize, slot, Argument, Instruction};
+use petgraph::algo::dominators::simple_fast;
+use petgraph::graphmap::DiGraphMap;
+use petgraph::Direction::Incoming;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Symbolic evaluation gives up after this many statements
+const MAX_STEPS: usize = 100_000;
+
+/// An instruction, or a loop fused by [`optimize`], that the analysis treats as a single unit
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Statement {
+    pub ip: usize,
+    pub instruction: Instruction,
+}
+
+impl Statement {
+    /// Where execution continues when it doesn't jump
+    pub fn next(&self) -> usize {
+        self.ip + self.instruction.length()
+    }
+}
+
+/// A natural loop: only entered through `header`, and repeated by jumping back from `latch`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Loop {
+    pub header: usize,
+    /// The last statement jumping back to the header
+    pub latch: usize,
+    /// Every statement of the loop, including those of nested loops
+    pub body: BTreeSet<usize>,
+}
+
+impl Loop {
+    /// The loop fills `header..=latch` and nothing else, so it can be written as a block
+    fn is_contiguous(&self) -> bool {
+        self.body
+            .iter()
+            .all(|ip| (self.header..=self.latch).contains(ip))
+    }
+}
+
+/// A value in terms of the initial contents of register `a`: `factor * a + offset`
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Linear {
+    pub factor: i32,
+    pub offset: i32,
+}
+
+impl Linear {
+    pub fn constant(value: i32) -> Self {
+        Self {
+            factor: 0,
+            offset: value,
+        }
+    }
+
+    fn value(self) -> Option<i32> {
+        (self.factor == 0).then_some(self.offset)
+    }
+
+    fn plus(self, other: Linear) -> Self {
+        Self {
+            factor: self.factor + other.factor,
+            offset: self.offset + other.offset,
+        }
+    }
+
+    fn times(self, value: i32) -> Self {
+        Self {
+            factor: self.factor * value,
+            offset: self.offset * value,
+        }
+    }
+}
+
+pub struct Analysis {
+    /// The program split into statements, keyed by the index of their first instruction
+    pub statements: BTreeMap<usize, Statement>,
+    /// Control flow between statements, the node `end` stands for halting
+    pub graph: DiGraphMap<usize, ()>,
+    /// One loop per header, ordered by header
+    pub loops: Vec<Loop>,
+    /// The index right after the last instruction
+    pub end: usize,
+}
+
+impl Analysis {
+    pub fn new(instructions: &[Instruction]) -> Self {
+        let end = instructions.len();
+        let statements = statements(instructions);
+        let mut graph = DiGraphMap::new();
+        graph.add_node(end);
+        for statement in statements.values() {
+            graph.add_node(statement.ip);
+            for successor in successors(statement, end) {
+                graph.add_edge(statement.ip, successor, ());
+            }
+        }
+        let loops = loops(&graph);
+        Self {
+            statements,
+            graph,
+            loops,
+            end,
+        }
+    }
+
+    /// `tgl` can rewrite the program while it runs, which makes the analysis a best guess
+    pub fn is_self_modifying(&self) -> bool {
+        self.statements
+            .values()
+            .any(|statement| matches!(statement.instruction, Toggle(_)))
+    }
+
+    /// The smallest loop containing a statement that matches
+    pub fn innermost_loop(&self, predicate: impl Fn(&Instruction) -> bool) -> Option<&Loop> {
+        self.loops
+            .iter()
+            .filter(|candidate| {
+                candidate
+                    .body
+                    .iter()
+                    .any(|ip| predicate(&self.statements[ip].instruction))
+            })
+            .min_by_key(|candidate| candidate.body.len())
+    }
+
+    /// Runs the program from the start with an unknown `a` and the other registers zeroed,
+    /// returning the registers once execution reaches `stop`.
+    /// `None` when getting there depends on `a`, goes through `tgl`, or doesn't happen at all.
+    pub fn evaluate_until(&self, stop: usize) -> Option<[Linear; 4]> {
+        let mut registers = [
+            Linear {
+                factor: 1,
+                offset: 0,
+            },
+            Linear::constant(0),
+            Linear::constant(0),
+            Linear::constant(0),
+        ];
+        let get = |registers: &[Linear; 4], argument: Argument| match argument {
+            Value(value) => Linear::constant(value),
+            Register(register) => registers[slot(register)],
+        };
+        let mut ip = 0;
+        for _ in 0..MAX_STEPS {
+            if ip == stop {
+                return Some(registers);
+            }
+            let statement = self.statements.get(&ip)?;
+            ip = statement.next();
+            match statement.instruction {
+                Copy(x, Register(y)) => registers[slot(y)] = get(&registers, x),
+                Increment(Register(x)) => {
+                    registers[slot(x)] = registers[slot(x)].plus(Linear::constant(1))
+                }
+                Decrement(Register(x)) => {
+                    registers[slot(x)] = registers[slot(x)].plus(Linear::constant(-1))
+                }
+                JumpNotZero(x, y) if get(&registers, x).value()? != 0 => {
+                    let offset = get(&registers, y).value()?;
+                    ip = usize::try_from(statement.ip as isize + offset as isize).ok()?;
+                }
+                Toggle(_) => return None,
+                Add(Register(x), Register(y)) => {
+                    registers[slot(x)] = registers[slot(x)].plus(registers[slot(y)]);
+                    registers[slot(y)] = Linear::constant(0);
+                }
+                Mul(Register(x), y, Register(t), Register(z)) => {
+                    let (y, z_value) = (get(&registers, y), registers[slot(z)]);
+                    let product = match (y.value(), z_value.value()) {
+                        (Some(y), _) => z_value.times(y),
+                        (_, Some(z)) => y.times(z),
+                        _ => return None,
+                    };
+                    registers[slot(x)] = registers[slot(x)].plus(product);
+                    registers[slot(t)] = Linear::constant(0);
+                    registers[slot(z)] = Linear::constant(0);
+                }
+                DivMod(Register(n), Value(d), Register(q), Register(r)) => {
+                    let dividend = registers[slot(n)].value()?;
+                    registers[slot(q)] = registers[slot(q)].plus(Linear::constant(dividend / d));
+                    registers[slot(r)] = Linear::constant(d - dividend % d);
+                    registers[slot(n)] = Linear::constant(0);
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Pseudo-code for the program: loops become `loop { .. }` blocks, jumps over code become `if`,
+    /// fused loops become arithmetic and any other jump is a `goto` to a label.
+    pub fn decompile(&self) -> String {
+        let mut decompiler = Decompiler {
+            analysis: self,
+            lines: vec![],
+            labels: BTreeSet::new(),
+        };
+        decompiler.block(0, self.end, 0, None);
+
+        let mut lines: Vec<(usize, String)> = vec![];
+        for line in decompiler.lines {
+            match line {
+                Line::Statement(ip, depth) if decompiler.labels.contains(&ip) => {
+                    lines.push((depth, format!("L{ip}:")))
+                }
+                Line::Statement(..) => {}
+                // Blocks of a single line fit on one line
+                Line::Text(depth, text) if text == "}" && lines.len() >= 2 => {
+                    let (inner_depth, inner) = &lines[lines.len() - 1];
+                    let (outer_depth, outer) = &lines[lines.len() - 2];
+                    if *inner_depth == depth + 1
+                        && *outer_depth == depth
+                        && outer.ends_with('{')
+                        && !inner.ends_with('{')
+                        && !inner.ends_with(':')
+                    {
+                        let (_, inner) = lines.pop().unwrap();
+                        lines.last_mut().unwrap().1 += &format!(" {inner} }}");
+                    } else {
+                        lines.push((depth, text));
+                    }
+                }
+                Line::Text(depth, text) => lines.push((depth, text)),
+            }
+        }
+
+        let mut pseudo_code = String::new();
+        if self.is_self_modifying() {
+            pseudo_code.push_str(
+                "// tgl rewrites this program while it runs, this is how it starts out\n",
+            );
+        }
+        for (depth, text) in lines {
+            pseudo_code.push_str(&format!("{}{text}\n", indent(depth)));
+        }
+        pseudo_code
+    }
+}
+
+/// Collapses fused loops into one statement, unless something outside of them jumps into their middle
+fn statements(instructions: &[Instruction]) -> BTreeMap<usize, Statement> {
+    let fused = optimize(instructions);
+    let jumps = instructions
+        .iter()
+        .enumerate()
+        .filter_map(|(ip, instruction)| Some((ip, jump_target(ip, *instruction)?)))
+        .collect::<Vec<_>>();
+
+    let mut statements = BTreeMap::new();
+    let mut ip = 0;
+    while ip < instructions.len() {
+        let instruction = fused[ip]
+            .filter(|loop_instruction| {
+                let region = ip..ip + loop_instruction.length();
+                jumps.iter().all(|&(from, to)| {
+                    region.contains(&from) || to <= ip as isize || to >= region.end as isize
+                })
+            })
+            .unwrap_or(instructions[ip]);
+        let statement = Statement { ip, instruction };
+        statements.insert(ip, statement);
+        ip = statement.next();
+    }
+    statements
+}
+
+/// The absolute target of a `jnz` by a constant offset, unless it can never jump
+fn jump_target(ip: usize, instruction: Instruction) -> Option<isize> {
+    match instruction {
+        JumpNotZero(Value(0), _) => None,
+        JumpNotZero(_, Value(offset)) => Some(ip as isize + offset as isize),
+        _ => None,
+    }
+}
+
+fn successors(statement: &Statement, end: usize) -> Vec<usize> {
+    let next = statement.next();
+    let target = || {
+        jump_target(statement.ip, statement.instruction)
+            .and_then(|target| usize::try_from(target).ok())
+            .filter(|target| *target < end)
+            .unwrap_or(end)
+    };
+    match statement.instruction {
+        JumpNotZero(Value(0), _) => vec![next],
+        JumpNotZero(Value(_), Value(_)) => vec![target()],
+        JumpNotZero(Register(_), Value(_)) => vec![next, target()],
+        // Jumping by a register could go anywhere, assume it leaves the program
+        JumpNotZero(_, Register(_)) => vec![next, end],
+        _ => vec![next],
+    }
+}
+
+/// Every edge to a statement that dominates its source closes a loop
+fn loops(graph: &DiGraphMap<usize, ()>) -> Vec<Loop> {
+    let dominators = simple_fast(graph, 0);
+    let mut loops: BTreeMap<usize, Loop> = BTreeMap::new();
+    for (latch, header, _) in graph.all_edges() {
+        let is_back_edge = dominators
+            .dominators(latch)
+            .is_some_and(|mut dominating| dominating.any(|ip| ip == header));
+        if !is_back_edge {
+            continue;
+        }
+        let found = loops.entry(header).or_insert_with(|| Loop {
+            header,
+            latch,
+            body: BTreeSet::new(),
+        });
+        found.latch = found.latch.max(latch);
+        found.body.extend(natural_loop(graph, header, latch));
+    }
+    loops.into_values().collect()
+}
+
+/// The header plus everything that reaches the latch without passing through the header
+fn natural_loop(graph: &DiGraphMap<usize, ()>, header: usize, latch: usize) -> BTreeSet<usize> {
+    let mut body = BTreeSet::from([header]);
+    let mut stack = vec![latch];
+    while let Some(ip) = stack.pop() {
+        if body.insert(ip) {
+            stack.extend(graph.neighbors_directed(ip, Incoming));
+        }
+    }
+    body
+}
+
+enum Line {
+    /// Where a statement starts, so a label can be put there
+    Statement(usize, usize),
+    Text(usize, String),
+}
+
+struct Decompiler<'a> {
+    analysis: &'a Analysis,
+    lines: Vec<Line>,
+    /// Statements that are the target of a `goto`
+    labels: BTreeSet<usize>,
+}
+
+impl<'a> Decompiler<'a> {
+    /// Writes the statements in `start..end`, which are inside of `current` if it is given
+    fn block(&mut self, start: usize, end: usize, depth: usize, current: Option<&'a Loop>) {
+        let mut ip = start;
+        while ip < end {
+            let Some(statement) = self.analysis.statements.get(&ip).copied() else {
+                break;
+            };
+            // The header of `current` was already marked right before its `loop {`
+            let is_header = current.is_some_and(|current| current.header == ip);
+            if !is_header {
+                self.lines.push(Line::Statement(ip, depth));
+            }
+            let nested = self.analysis.loops.iter().find(|nested| {
+                nested.header == ip && nested.latch < end && nested.is_contiguous() && !is_header
+            });
+            ip = match nested {
+                Some(nested) => {
+                    let exit = self.analysis.statements[&nested.latch].next();
+                    self.text(depth, "loop {");
+                    self.block(ip, exit, depth + 1, Some(nested));
+                    self.text(depth, "}");
+                    exit
+                }
+                None => self.statement(statement, end, depth, current),
+            };
+        }
+    }
+
+    /// Writes a single statement, returning where the block continues
+    fn statement(
+        &mut self,
+        statement: Statement,
+        end: usize,
+        depth: usize,
+        current: Option<&'a Loop>,
+    ) -> usize {
+        let next = statement.next();
+        let (condition, offset) = match statement.instruction {
+            JumpNotZero(Value(0), _) => return next,
+            JumpNotZero(x, Value(offset)) => (x, offset),
+            JumpNotZero(x, Register(offset)) => {
+                self.jump(depth, x, &format!("jump by {offset}"));
+                return next;
+            }
+            instruction => {
+                for text in expressions(statement.ip, instruction) {
+                    self.text(depth, &text);
+                }
+                return next;
+            }
+        };
+        let target = statement.ip as isize + offset as isize;
+
+        if let Some(current) = current {
+            let exit = self.analysis.statements[&current.latch].next() as isize;
+            if target == current.header as isize && statement.ip == current.latch {
+                if let Register(register) = condition {
+                    self.text(depth, &format!("if {register} == 0 {{ break }}"));
+                }
+                return next;
+            } else if target == current.header as isize {
+                self.jump(depth, condition, "continue");
+                return next;
+            } else if target == exit {
+                self.jump(depth, condition, "break");
+                return next;
+            }
+        }
+
+        match condition {
+            Register(register) if target > next as isize && target <= end as isize => {
+                self.text(depth, &format!("if {register} == 0 {{"));
+                self.block(next, target as usize, depth + 1, current);
+                self.text(depth, "}");
+                target as usize
+            }
+            _ => {
+                match usize::try_from(target)
+                    .ok()
+                    .filter(|target| *target < self.analysis.end)
+                {
+                    Some(target) => {
+                        self.labels.insert(target);
+                        self.jump(depth, condition, &format!("goto L{target}"));
+                    }
+                    None => self.jump(depth, condition, "halt"),
+                }
+                next
+            }
+        }
+    }
+
+    /// `text` always when the condition is a constant, otherwise only when the register isn't zero
+    fn jump(&mut self, depth: usize, condition: Argument, text: &str) {
+        match condition {
+            Value(_) => self.text(depth, text),
+            Register(register) => self.text(depth, &format!("if {register} != 0 {{ {text} }}")),
+        }
+    }
+
+    fn text(&mut self, depth: usize, text: &str) {
+        self.lines.push(Line::Text(depth, text.to_string()));
+    }
+}
+
+fn expressions(ip: usize, instruction: Instruction) -> Vec<String> {
+    match instruction {
+        Copy(x, Register(y)) => vec![format!("{y} = {x}")],
+        Increment(Register(x)) => vec![format!("{x} += 1")],
+        Decrement(Register(x)) => vec![format!("{x} -= 1")],
+        Toggle(x) => vec![format!("toggle {ip} + {x}")],
+        Out(x) => vec![format!("out {x}")],
+        Add(x, y) => vec![format!("{x} += {y}"), format!("{y} = 0")],
+        Mul(x, y, t, z) => vec![
+            format!("{x} += {y} * {z}"),
+            format!("{t} = 0"),
+            format!("{z} = 0"),
+        ],
+        DivMod(n, d, q, r) => vec![
+            format!("{q} += {n} / {d}"),
+            format!("{r} = {d} - {n} % {d}"),
+            format!("{n} = 0"),
+        ],
+        // Invalid instructions, which only `tgl` creates, are skipped
+        _ => vec![],
+    }
+}
+
+fn indent(depth: usize) -> String {
+    "    ".repeat(depth)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembunny::instructions;
+
+    const CLOCK: &str = "cpy a d\ncpy 4 c\ncpy 633 b\ninc d\ndec b\njnz b -2\ndec c\njnz c -5\n\
+        cpy d a\njnz 0 0\ncpy a b\ncpy 0 a\ncpy 2 c\njnz b 2\njnz 1 6\ndec b\ndec c\njnz c -4\n\
+        inc a\njnz 1 -7\ncpy 2 b\njnz c 2\njnz 1 4\ndec b\ndec c\njnz 1 -4\njnz 0 0\nout b\n\
+        jnz a -19\njnz 1 -21";
+
+    #[test]
+    fn test_loops() {
+        let analysis = Analysis::new(&instructions(CLOCK).unwrap().1);
+        let loops = analysis
+            .loops
+            .iter()
+            .map(|found| (found.header, found.latch))
+            .collect::<Vec<_>>();
+        assert_eq!(loops, vec![(8, 29), (9, 28), (21, 25)]);
+        assert_eq!(
+            analysis
+                .innermost_loop(|instruction| matches!(instruction, Out(_)))
+                .map(|found| found.header),
+            Some(9)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_until() {
+        let analysis = Analysis::new(&instructions(CLOCK).unwrap().1);
+        let [a, b, c, d] = analysis.evaluate_until(9).unwrap();
+        assert_eq!(
+            a,
+            Linear {
+                factor: 1,
+                offset: 2532
+            }
+        );
+        assert_eq!([b, c], [Linear::constant(0), Linear::constant(0)]);
+        assert_eq!(d, a);
+        // Past the division, the flow depends on the value of `a`
+        assert_eq!(analysis.evaluate_until(20), None);
+    }
+
+    #[test]
+    fn test_decompile() {
+        let analysis = Analysis::new(&instructions(CLOCK).unwrap().1);
+        assert_eq!(
+            analysis.decompile(),
+            "d = a\nc = 4\nd += 633 * c\nb = 0\nc = 0\nloop {\n    a = d\n    loop {\n        \
+            b = a\n        a = 0\n        a += b / 2\n        c = 2 - b % 2\n        b = 0\n        \
+            b = 2\n        loop {\n            if c == 0 { break }\n            b -= 1\n            \
+            c -= 1\n        }\n        out b\n        if a == 0 { break }\n    }\n}\n"
+        );
+
+        let program = "cpy 1 a\njnz c 2\njnz 1 3\ninc a\njnz d -3\nout a\njnz a 5\njnz 1 -2";
+        assert_eq!(
+            Analysis::new(&instructions(program).unwrap().1).decompile(),
+            "a = 1\nloop {\n    if c == 0 { break }\n    a += 1\n    if d == 0 { break }\n}\n\
+            loop {\n    out a\n    if a != 0 { halt }\n}\n"
+        );
+
+        let program = "cpy 1 a\njnz c 2\njnz 1 3\ninc a\njnz d -4\nout a\njnz 1 -6";
+        assert_eq!(
+            Analysis::new(&instructions(program).unwrap().1).decompile(),
+            "loop {\n    a = 1\n    if c == 0 { goto L5 }\n    a += 1\n    \
+            if d != 0 { continue }\n    L5:\n    out a\n}\n"
+        );
+    }
+}
@@ -0,0 +1,316 @@
+/*
+ * Remembers how long every part took, so that later runs can tell whether they got slower.
+ */
+use crate::days::Options;
+use crate::report::{Format, Record};
+use crate::{manifest_path, parse_file_or_default, InputSource, ANSI_BOLD, ANSI_RED, ANSI_RESET};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+pub const BASELINE_PATH: &str = ".bench/baseline.csv";
+
+/// Percent a part may get slower before it counts as a regression
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+type Key = (u8, u8, String);
+
+/// Timings of solved parts, by day, part and solver
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Baseline {
+    timings: BTreeMap<Key, Duration>,
+}
+
+impl Baseline {
+    /// An empty baseline if there is no file yet
+    pub fn load(path: &Path) -> io::Result<Baseline> {
+        parse_file_or_default(path)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_string())
+    }
+
+    /// Keeps the timings of parts that weren't solved this time
+    pub fn update(&mut self, records: &[Record]) {
+        for record in records.iter().filter(|record| record.answer().is_some()) {
+            self.timings.insert(key(record), record.elapsed);
+        }
+    }
+
+    /// Every part that has a baseline, including the ones that no longer give an answer
+    pub fn compare(&self, records: &[Record]) -> Vec<Change> {
+        records
+            .iter()
+            .filter_map(|record| {
+                Some(Change {
+                    day: record.day,
+                    part: record.part,
+                    solver: record.solver,
+                    baseline: *self.timings.get(&key(record))?,
+                    current: record.answer().is_some().then_some(record.elapsed),
+                })
+            })
+            .collect()
+    }
+}
+
+fn key(record: &Record) -> Key {
+    (record.day, record.part, record.solver.to_string())
+}
+
+impl FromStr for Baseline {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |line: &str| {
+            io::Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid baseline entry \"{line}\""),
+            )
+        };
+        let timings = s
+            .lines()
+            .skip(1)
+            .map(|line| {
+                let fields: Vec<_> = line.split(',').collect();
+                match fields[..] {
+                    [day, part, solver, nanos] => Ok((
+                        (
+                            day.parse().map_err(|_| invalid(line))?,
+                            part.parse().map_err(|_| invalid(line))?,
+                            solver.to_string(),
+                        ),
+                        Duration::from_nanos(nanos.parse().map_err(|_| invalid(line))?),
+                    )),
+                    _ => Err(invalid(line)),
+                }
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(Baseline { timings })
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "day,part,solver,elapsed_ns")?;
+        for ((day, part, solver), elapsed) in &self.timings {
+            writeln!(f, "{day},{part},{solver},{}", elapsed.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+/// How a part's timing compares to its baseline
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub day: u8,
+    pub part: u8,
+    pub solver: &'static str,
+    pub baseline: Duration,
+    /// `None` when the part failed, panicked, timed out or gave no answer this time
+    pub current: Option<Duration>,
+}
+
+impl Change {
+    /// Positive when the part got slower, `None` when it gave no answer
+    pub fn percent(&self) -> Option<f64> {
+        let current = self.current?.as_secs_f64();
+        let baseline = self.baseline.as_secs_f64();
+        if baseline == 0.0 {
+            return Some(0.0);
+        }
+        Some((current - baseline) / baseline * 100.0)
+    }
+
+    /// A part that used to give an answer and no longer does has regressed too
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent().is_none_or(|percent| percent > threshold)
+    }
+}
+
+/// One row per change, regressions in red
+pub fn diff_table(changes: &[Change], threshold: f64) -> String {
+    let mut table = format!(
+        "{ANSI_BOLD}{:>3}  {:>4}  {:<14}  {:>10}  {:>10}  {:>8}{ANSI_RESET}\n",
+        "Day", "Part", "Solver", "Baseline", "Current", "Change"
+    );
+    for change in changes {
+        let (current, percent) = match (change.current, change.percent()) {
+            (Some(current), Some(percent)) => {
+                (format!("{current:.2?}"), format!("{percent:>+7.1}%"))
+            }
+            _ => ("no answer".to_string(), format!("{:>8}", "-")),
+        };
+        let row = format!(
+            "{:>3}  {:>4}  {:<14}  {:>10}  {:>10}  {percent}",
+            change.day,
+            change.part,
+            change.solver,
+            format!("{:.2?}", change.baseline),
+            current,
+        );
+        if change.is_regression(threshold) {
+            table.push_str(&format!("{ANSI_RED}{row}  regression{ANSI_RESET}\n"));
+        } else {
+            table.push_str(&row);
+            table.push('\n');
+        }
+    }
+    table
+}
+
+/// Compares against and then saves the baseline, as `options` ask.
+/// Returns whether any part regressed, the diff table goes to stderr unless printing text.
+/// Timings for another input than `src/inputs` are left out.
+pub fn check(records: &[Record], options: &Options) -> bool {
+    if !options.compare && !options.save_baseline || options.input != InputSource::Default {
+        return false;
+    }
+    let path = &manifest_path(BASELINE_PATH);
+    let mut baseline = match Baseline::load(path) {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("{ANSI_RED}error:{ANSI_RESET} could not read \"{BASELINE_PATH}\": {e}");
+            return options.compare;
+        }
+    };
+
+    let mut regressed = false;
+    if options.compare {
+        let changes = baseline.compare(records);
+        // Comparing with nothing can't show that nothing got slower
+        regressed = changes.is_empty()
+            || changes
+                .iter()
+                .any(|change| change.is_regression(options.threshold));
+        let table = diff_table(&changes, options.threshold);
+        let summary = match changes.len() {
+            0 => format!("No baseline to compare with in \"{BASELINE_PATH}\"."),
+            _ => format!(
+                "{} of {} parts got more than {}% slower or no longer give an answer.",
+                changes
+                    .iter()
+                    .filter(|change| change.is_regression(options.threshold))
+                    .count(),
+                changes.len(),
+                options.threshold
+            ),
+        };
+        if options.format == Format::Text {
+            print!("{table}");
+            println!("{summary}");
+        } else {
+            eprint!("{table}");
+            eprintln!("{summary}");
+        }
+    }
+
+    if options.save_baseline {
+        baseline.update(records);
+        if let Err(e) = baseline.save(path) {
+            eprintln!("{ANSI_RED}error:{ANSI_RESET} could not write \"{BASELINE_PATH}\": {e}");
+        }
+    }
+    regressed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Outcome;
+
+    #[test]
+    fn test_round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.update(&[
+            Record::new(
+                5,
+                1,
+                Outcome::Solved("1".to_string()),
+                Duration::from_micros(100),
+            ),
+            Record::new(14, 1, Outcome::NotSolved, Duration::from_micros(100)),
+        ]);
+        assert_eq!(
+            baseline.to_string(),
+            "day,part,solver,elapsed_ns\n5,1,part_one,100000\n"
+        );
+        assert_eq!(baseline.to_string().parse::<Baseline>().unwrap(), baseline);
+        assert!("day,part,solver,elapsed_ns\n5,1,part_one"
+            .parse::<Baseline>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut baseline = Baseline::default();
+        baseline.update(&[
+            Record::new(
+                5,
+                1,
+                Outcome::Solved("1".to_string()),
+                Duration::from_micros(100),
+            ),
+            Record::new(
+                14,
+                1,
+                Outcome::Solved("1".to_string()),
+                Duration::from_micros(100),
+            ),
+        ]);
+        let changes = baseline.compare(&[
+            Record::new(
+                5,
+                1,
+                Outcome::Solved("1".to_string()),
+                Duration::from_micros(120),
+            ),
+            Record::new(
+                14,
+                1,
+                Outcome::Solved("1".to_string()),
+                Duration::from_micros(95),
+            ),
+            Record::new(
+                16,
+                1,
+                Outcome::Solved("1".to_string()),
+                Duration::from_micros(100),
+            ),
+        ]);
+        assert_eq!(changes.len(), 2);
+        assert!((changes[0].percent().unwrap() - 20.0).abs() < 1e-9);
+        assert!(changes[0].is_regression(DEFAULT_THRESHOLD));
+        assert!(!changes[1].is_regression(DEFAULT_THRESHOLD));
+        assert!(diff_table(&changes, DEFAULT_THRESHOLD).contains("+20.0%  regression"));
+
+        let changes = baseline.compare(&[
+            Record::new(
+                5,
+                1,
+                Outcome::Failed("bad input".to_string()),
+                Duration::from_micros(1),
+            ),
+            Record::new(
+                14,
+                1,
+                Outcome::TimedOut(Duration::from_secs(1)),
+                Duration::from_micros(1_000_000),
+            ),
+        ]);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[1].current, None);
+        assert!(changes
+            .iter()
+            .all(|change| change.is_regression(DEFAULT_THRESHOLD)));
+        assert!(diff_table(&changes, DEFAULT_THRESHOLD).contains("no answer         -  regression"));
+    }
+}
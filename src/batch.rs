@@ -0,0 +1,249 @@
+/*
+ * Solves a day against many inputs at once, e.g. other accounts' inputs or every example.
+ */
+use crate::answers::{self, Answers, Verdict, ANSWERS_PATH};
+use crate::days::{Day, Options};
+use crate::report::Record;
+use crate::{manifest_path, Outcome, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+/// An input with the answers it should give, by part. Other than the default input,
+/// whose answers are recorded with `--record`, inputs keep them next to themselves:
+/// `src/inputs/05/alice.txt` in `src/inputs/05/alice.answers`, one `<part>: <answer>` per line.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct NamedInput {
+    /// The path below `src`, without extension
+    pub name: String,
+    pub path: PathBuf,
+    pub expected: BTreeMap<u8, String>,
+}
+
+impl NamedInput {
+    fn new(name: String, path: PathBuf) -> io::Result<NamedInput> {
+        let expected = match fs::read_to_string(path.with_extension("answers")) {
+            Ok(answers) => parse_expected(&answers)?,
+            Err(e) if e.kind() == ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(NamedInput {
+            name,
+            path,
+            expected,
+        })
+    }
+}
+
+fn parse_expected(answers: &str) -> io::Result<BTreeMap<u8, String>> {
+    answers
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            line.split_once(": ")
+                .and_then(|(part, answer)| Some((part.parse().ok()?, answers::unescape(answer))))
+                .ok_or_else(|| {
+                    io::Error::new(
+                        ErrorKind::InvalidData,
+                        format!("invalid answer entry \"{line}\""),
+                    )
+                })
+        })
+        .collect()
+}
+
+/// The default input, every input in `src/inputs/NN/` and, if asked, every example
+/// of the day like `src/examples/03.txt` and `src/examples/03b.txt`
+pub fn inputs(day: u8, examples: bool) -> io::Result<Vec<NamedInput>> {
+    let src = manifest_path("src");
+    let day_padded = format!("{day:02}");
+    let mut inputs = vec![];
+
+    let default = src.join("inputs").join(format!("{day_padded}.txt"));
+    if default.exists() {
+        let mut input = NamedInput::new(format!("inputs/{day_padded}"), default)?;
+        let recorded = Answers::load(&manifest_path(ANSWERS_PATH))?;
+        input.expected = [1, 2]
+            .into_iter()
+            .filter_map(|part| Some((part, recorded.get(day, part)?.to_string())))
+            .collect();
+        inputs.push(input);
+    }
+
+    for path in text_files(&src.join("inputs").join(&day_padded))? {
+        let name = format!("inputs/{day_padded}/{}", stem(&path));
+        inputs.push(NamedInput::new(name, path)?);
+    }
+
+    if examples {
+        for path in text_files(&src.join("examples"))? {
+            let stem = stem(&path);
+            let suffix = stem.strip_prefix(&day_padded);
+            if suffix.is_some_and(|suffix| !suffix.starts_with(|c: char| c.is_ascii_digit())) {
+                inputs.push(NamedInput::new(format!("examples/{stem}"), path)?);
+            }
+        }
+    }
+    Ok(inputs)
+}
+
+/// Sorted by name, none if there is no such directory
+fn text_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+    let mut files = entries
+        .map(|entry| Ok(entry?.path()))
+        .filter(|path| {
+            path.as_ref().map_or(true, |path| {
+                path.extension().is_some_and(|ext| ext == "txt")
+            })
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+    files.sort();
+    Ok(files)
+}
+
+fn stem(path: &Path) -> String {
+    path.file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Solves the day against every input, marking answers against the expected ones
+pub fn run(day: &Day, inputs: &[NamedInput], options: &Options) -> Vec<Vec<Record>> {
+    inputs
+        .iter()
+        .map(|input| {
+            let mut records = match fs::read_to_string(&input.path) {
+                Ok(contents) => day.run(&contents, options),
+                Err(e) => day
+                    .selected(options)
+                    .map(|part| Record {
+                        day: day.day,
+                        part: part.part,
+                        solver: part.name,
+                        outcome: Outcome::Failed(format!("could not read input: {e}")),
+                        elapsed: Default::default(),
+                        stats: None,
+                        expected: None,
+                    })
+                    .collect(),
+            };
+            for record in &mut records {
+                record.expected = input.expected.get(&record.part).cloned();
+            }
+            records
+        })
+        .collect()
+}
+
+/// One row per input and one column per solver
+pub fn table(inputs: &[NamedInput], results: &[Vec<Record>]) -> String {
+    let mut rows = vec![];
+    let header = results.first().map_or(vec![], |records| {
+        records
+            .iter()
+            .map(|record| format!("Part {} ({})", record.part, record.solver))
+            .collect()
+    });
+    rows.push((
+        "Input".to_string(),
+        header
+            .into_iter()
+            .map(|cell| (cell, ""))
+            .collect::<Vec<_>>(),
+    ));
+
+    for (input, records) in inputs.iter().zip(results) {
+        let cells = records
+            .iter()
+            .map(|record| match (record.answer(), record.verdict()) {
+                (Some(answer), Verdict::Matches) => (format!("{} ✔", cell(answer)), ANSI_GREEN),
+                (Some(answer), Verdict::Differs) => (format!("{} ✘", cell(answer)), ANSI_RED),
+                (Some(answer), Verdict::Unknown) => (format!("{} ?", cell(answer)), ""),
+                (None, _) if record.is_failure() => (record.status().to_string(), ANSI_RED),
+                (None, _) => (record.status().to_string(), ""),
+            })
+            .collect();
+        rows.push((input.name.clone(), cells));
+    }
+
+    let width = |column: usize| {
+        rows.iter()
+            .filter_map(|(_, cells)| cells.get(column))
+            .map(|(cell, _)| cell.chars().count())
+            .max()
+            .unwrap_or(0)
+    };
+    let name_width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+
+    let mut table = String::new();
+    for (row, (name, cells)) in rows.iter().enumerate() {
+        let mut line = format!("{name:<name_width$}");
+        for (column, (cell, color)) in cells.iter().enumerate() {
+            let padding = " ".repeat(width(column) - cell.chars().count());
+            match *color {
+                "" => line.push_str(&format!("  {cell}{padding}")),
+                _ => line.push_str(&format!("  {color}{cell}{ANSI_RESET}{padding}")),
+            }
+        }
+        match row {
+            0 => table.push_str(&format!("{ANSI_BOLD}{}{ANSI_RESET}\n", line.trim_end())),
+            _ => table.push_str(&format!("{}\n", line.trim_end())),
+        }
+    }
+    table
+}
+
+/// Answers spanning several lines, like rendered letters, don't fit in a cell
+fn cell(answer: &str) -> String {
+    match answer.lines().count() {
+        0 | 1 => answer.to_string(),
+        lines => format!("<{lines} lines>"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn test_parse_expected() {
+        assert_eq!(
+            parse_expected("1: 3\n2: #.\\n.#\n").unwrap(),
+            BTreeMap::from([(1, "3".to_string()), (2, "#.\n.#".to_string())])
+        );
+        assert!(parse_expected("one: 3").is_err());
+    }
+
+    #[test]
+    fn test_examples() {
+        let names = inputs(3, true).unwrap().into_iter().map(|input| input.name);
+        assert!(names
+            .filter(|name| name.starts_with("examples"))
+            .eq(["examples/03", "examples/03b"]));
+    }
+
+    #[test]
+    fn test_run() {
+        let inputs = [NamedInput {
+            name: "examples/19".to_string(),
+            path: manifest_path("src/examples/19.txt"),
+            expected: BTreeMap::from([(1, "3".to_string()), (2, "3".to_string())]),
+        }];
+        let results = run(days::get(19).unwrap(), &inputs, &Options::default());
+        let verdicts = results[0].iter().map(Record::verdict).collect::<Vec<_>>();
+        assert_eq!(verdicts, [Verdict::Matches, Verdict::Differs]);
+
+        let table = table(&inputs, &results);
+        assert!(table.contains("examples/19"));
+        assert!(table.contains("3 ✔"));
+        assert!(table.contains("2 ✘"));
+    }
+}
@@ -0,0 +1,107 @@
+/*
+ * Times a solver over many runs, so that fast parts get more than one noisy measurement.
+ */
+use crate::Outcome;
+use std::time::Duration;
+
+/// Summary of the timed runs of a part
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    /// 95th percentile, by nearest rank
+    pub p95: Duration,
+    /// Population standard deviation
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// `None` without any samples
+    pub fn new(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut samples = samples.to_vec();
+        samples.sort();
+        let runs = samples.len();
+        let nanos = |duration: &Duration| duration.as_nanos() as f64;
+
+        let mean = samples.iter().map(nanos).sum::<f64>() / runs as f64;
+        let variance = samples
+            .iter()
+            .map(|sample| (nanos(sample) - mean).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+        let median = match runs % 2 {
+            0 => (samples[runs / 2 - 1] + samples[runs / 2]) / 2,
+            _ => samples[runs / 2],
+        };
+        let rank = (runs as f64 * 0.95).ceil() as usize;
+
+        Some(Stats {
+            runs,
+            min: samples[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            p95: samples[rank - 1],
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        })
+    }
+}
+
+/// Warms up with a tenth of `runs`, then times `runs` runs. Stops at the first run that
+/// doesn't solve the part, whose outcome is returned without stats.
+pub fn bench(
+    solver: impl Fn(&str) -> (Outcome, Duration),
+    input: &str,
+    runs: usize,
+) -> (Outcome, Option<Stats>) {
+    let runs = runs.max(1);
+    let warmup = runs.div_ceil(10);
+    let mut samples = Vec::with_capacity(runs);
+    let mut outcome = Outcome::NotSolved;
+
+    for run in 0..warmup + runs {
+        let elapsed;
+        (outcome, elapsed) = solver(input);
+        if !matches!(outcome, Outcome::Solved(_)) {
+            return (outcome, None);
+        }
+        if run >= warmup {
+            samples.push(elapsed);
+        }
+    }
+    (outcome, Stats::new(&samples))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = [5, 1, 4, 2, 3, 100].map(Duration::from_micros);
+        let stats = Stats::new(&samples).unwrap();
+        assert_eq!(stats.runs, 6);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_nanos(3500));
+        assert_eq!(stats.mean, Duration::from_nanos(19_167));
+        assert_eq!(stats.p95, Duration::from_micros(100));
+        assert_eq!(stats.stddev, Duration::from_nanos(36_173));
+
+        assert_eq!(Stats::new(&[]), None);
+    }
+
+    #[test]
+    fn test_bench() {
+        let solved = |_: &str| (Outcome::Solved("1".to_string()), Duration::from_micros(1));
+        let (outcome, stats) = bench(solved, "", 20);
+        assert_eq!(outcome, Outcome::Solved("1".to_string()));
+        assert_eq!(stats.map(|stats| stats.runs), Some(20));
+
+        let failed = |_: &str| (Outcome::Failed("bad input".to_string()), Duration::ZERO);
+        assert_eq!(bench(failed, "", 20).1, None);
+    }
+}
@@ -0,0 +1,223 @@
+/*
+ * Step debugger for assembunny programs (days 12, 23 and 25).
+ * example: `cargo assembunny src/inputs/23.txt -a 7` or `cargo assembunny src/inputs/25.txt --trace 200`
+ * `cargo assembunny src/inputs/25.txt --decompile` prints the program as pseudo-code instead.
+ */
+use advent_of_code::assembunny::analysis::Analysis;
+use advent_of_code::assembunny::{instructions, print, Breakpoint, Comparison, Computer, Step};
+use advent_of_code::helpers::parse;
+use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::io::{stdin, stdout, BufRead, Write};
+use std::{fs, process};
+
+const HELP: &str = "\
+s, step [n]            run the next n instructions (default 1)
+c, continue            run until a breakpoint is hit or the program halts
+b, break <index>       stop before the instruction at <index> runs
+b, break <r> <op> <n>  stop once register <r> compares to <n>, <op> is one of == != < <= > >=
+d, delete <n>          remove breakpoint number <n>
+i, info                list breakpoints
+r, registers           show the registers
+l, list                show the program, including instructions modified by `tgl`
+set <r> <n>            store <n> in register <r>
+dump <file>            write the program, including instructions modified by `tgl`, to <file>
+q, quit";
+
+struct Args {
+    path: String,
+    registers: Vec<(char, i32)>,
+    optimize: bool,
+    decompile: bool,
+    trace: Option<usize>,
+    max_output: usize,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let mut registers = vec![];
+    for (register, key) in [('a', "-a"), ('b', "-b"), ('c', "-c"), ('d', "-d")] {
+        if let Some(value) = args.opt_value_from_str(key)? {
+            registers.push((register, value));
+        }
+    }
+    Ok(Args {
+        optimize: args.contains(["-O", "--optimize"]),
+        decompile: args.contains("--decompile"),
+        trace: args.opt_value_from_str(["-t", "--trace"])?,
+        max_output: args
+            .opt_value_from_str("--max-output")?
+            .unwrap_or(usize::MAX),
+        registers,
+        path: args.free_from_str()?,
+    })
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            process::exit(1);
+        }
+    };
+
+    let input = match fs::read_to_string(&args.path) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Failed to read \"{}\": {e}", args.path);
+            process::exit(1);
+        }
+    };
+    let program = match parse(instructions, &input) {
+        Ok(program) => program,
+        Err(e) => {
+            eprintln!("Failed to parse \"{}\": {e}", args.path);
+            process::exit(1);
+        }
+    };
+
+    if args.decompile {
+        print!("{}", Analysis::new(&program).decompile());
+        return;
+    }
+
+    let mut computer = if args.optimize {
+        Computer::optimized(program)
+    } else {
+        Computer::new(program)
+    };
+    for (register, value) in args.registers {
+        computer.set(register, value);
+    }
+
+    match args.trace {
+        Some(max_steps) => println!("{}", computer.trace(max_steps, args.max_output)),
+        None => repl(&mut computer),
+    }
+}
+
+fn repl(computer: &mut Computer) {
+    let mut breakpoints: Vec<Breakpoint> = vec![];
+    print_registers(computer);
+    prompt();
+
+    for line in stdin().lock().lines() {
+        let Ok(line) = line else { break };
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        match words.as_slice() {
+            [] => {}
+            ["s" | "step", rest @ ..] => {
+                let count = rest.first().and_then(|n| n.parse().ok()).unwrap_or(1);
+                for _ in 0..count {
+                    let ip = computer.ip();
+                    let step = computer.step(&mut print_output);
+                    match step {
+                        Step::Ran(instruction) | Step::Interrupted(instruction) => {
+                            println!("{ip:>4}  {instruction}")
+                        }
+                        Step::Halted => {
+                            println!("halted.");
+                            break;
+                        }
+                    }
+                }
+                print_registers(computer);
+            }
+            ["c" | "continue"] => {
+                match computer.run_until(&breakpoints, &mut print_output) {
+                    Step::Halted => println!("halted."),
+                    _ => println!("stopped at {}.", computer.ip()),
+                }
+                print_registers(computer);
+            }
+            ["b" | "break", rest @ ..] => match parse_breakpoint(rest) {
+                Some(breakpoint) => {
+                    breakpoints.push(breakpoint);
+                    println!("breakpoint {}: {breakpoint:?}", breakpoints.len());
+                }
+                None => println!("usage: break <index> | break <register> <op> <value>"),
+            },
+            ["d" | "delete", n] => match n.parse::<usize>() {
+                Ok(n) if (1..=breakpoints.len()).contains(&n) => {
+                    breakpoints.remove(n - 1);
+                }
+                _ => println!("no breakpoint {n}."),
+            },
+            ["i" | "info"] => {
+                for (n, breakpoint) in breakpoints.iter().enumerate() {
+                    println!("{}: {breakpoint:?}", n + 1);
+                }
+            }
+            ["r" | "registers"] => print_registers(computer),
+            ["l" | "list"] => {
+                for (idx, instruction) in computer.instructions().iter().enumerate() {
+                    let current = if computer.ip() == idx as isize {
+                        '>'
+                    } else {
+                        ' '
+                    };
+                    let stop = if breakpoints.contains(&Breakpoint::Address(idx)) {
+                        '*'
+                    } else {
+                        ' '
+                    };
+                    println!("{current}{stop}{idx:>3}  {instruction}");
+                }
+            }
+            ["set", register, value] => match (parse_register(register), value.parse()) {
+                (Some(register), Ok(value)) => computer.set(register, value),
+                _ => println!("usage: set <register> <value>"),
+            },
+            ["dump", path] => match fs::write(path, print(computer.instructions()) + "\n") {
+                Ok(()) => println!("wrote {path}."),
+                Err(e) => println!("failed to write \"{path}\": {e}"),
+            },
+            ["q" | "quit"] => break,
+            ["h" | "help"] => println!("{HELP}"),
+            _ => println!("unknown command, type `help` for a list of commands."),
+        }
+        prompt();
+    }
+}
+
+fn prompt() {
+    print!("{ANSI_BOLD}(assembunny){ANSI_RESET} ");
+    stdout().flush().unwrap();
+}
+
+fn print_output(value: i32) -> bool {
+    println!("{ANSI_ITALIC}out: {value}{ANSI_RESET}");
+    true
+}
+
+fn print_registers(computer: &Computer) {
+    let [a, b, c, d] = computer.registers();
+    println!("ip={} a={a} b={b} c={c} d={d}", computer.ip());
+}
+
+fn parse_register(register: &str) -> Option<char> {
+    match register {
+        "a" | "b" | "c" | "d" => register.chars().next(),
+        _ => None,
+    }
+}
+
+fn parse_breakpoint(words: &[&str]) -> Option<Breakpoint> {
+    match words {
+        [index] => index.parse().ok().map(Breakpoint::Address),
+        [register, comparison, value] => Some(Breakpoint::Register(
+            parse_register(register)?,
+            match *comparison {
+                "==" => Comparison::Equal,
+                "!=" => Comparison::NotEqual,
+                "<" => Comparison::Less,
+                "<=" => Comparison::LessOrEqual,
+                ">" => Comparison::Greater,
+                ">=" => Comparison::GreaterOrEqual,
+                _ => return None,
+            },
+            value.parse().ok()?,
+        )),
+        _ => None,
+    }
+}
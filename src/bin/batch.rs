@@ -0,0 +1,101 @@
+/*
+ * Solves a day against all of its inputs and prints a table of the answers.
+ * example: `cargo batch 5` or `cargo batch 3 --examples --part 1`
+ * Inputs besides `src/inputs/NN.txt` go in `src/inputs/NN/`, their answers in a `.answers` file next to them.
+ */
+use advent_of_code::answers::Verdict;
+use advent_of_code::batch;
+use advent_of_code::days::{self, Options};
+use advent_of_code::report::Record;
+use advent_of_code::{ANSI_RED, ANSI_RESET};
+use std::process;
+
+struct Args {
+    day: u8,
+    examples: bool,
+    options: Options,
+}
+
+/// Flags of `cargo solve` that make no sense for a table of answers
+const UNSUPPORTED: [&str; 6] = [
+    "--format",
+    "--record",
+    "--verify",
+    "--compare",
+    "--save-baseline",
+    "--input",
+];
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    if let Some(flag) = UNSUPPORTED.into_iter().find(|&flag| args.contains(flag)) {
+        return Err(pico_args::Error::ArgumentParsingFailed {
+            cause: format!("{flag} is not supported by batch"),
+        });
+    }
+    let parsed = Args {
+        examples: args.contains(["-e", "--examples"]),
+        options: Options::from_args(&mut args)?,
+        day: args.free_from_str()?,
+    };
+    match args.finish().as_slice() {
+        [] => Ok(parsed),
+        unused => Err(pico_args::Error::ArgumentParsingFailed {
+            cause: format!("unexpected arguments {unused:?}"),
+        }),
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            process::exit(1);
+        }
+    };
+
+    let Some(day) = days::get(args.day) else {
+        eprintln!(
+            "{ANSI_RED}error:{ANSI_RESET} day {} is not solved yet",
+            args.day
+        );
+        process::exit(1);
+    };
+    let inputs = match batch::inputs(args.day, args.examples) {
+        Ok(inputs) => inputs,
+        Err(e) => {
+            eprintln!(
+                "{ANSI_RED}error:{ANSI_RESET} could not read the inputs of day {}: {e}",
+                args.day
+            );
+            process::exit(1);
+        }
+    };
+    if inputs.is_empty() {
+        eprintln!("No inputs for day {}.", args.day);
+        process::exit(1);
+    }
+
+    let results = batch::run(day, &inputs, &args.options);
+    print!("{}", batch::table(&inputs, &results));
+
+    let differs = results
+        .iter()
+        .flatten()
+        .filter(|record| record.verdict() == Verdict::Differs)
+        .count();
+    let expected = results
+        .iter()
+        .flatten()
+        .filter_map(|record: &Record| record.expected.as_ref())
+        .count();
+    match differs {
+        _ if expected == 0 => println!("No expected answers to compare with."),
+        0 => println!("All {expected} expected answers match."),
+        _ => {
+            println!("{ANSI_RED}{differs} of {expected} expected answers differ.{ANSI_RESET}");
+            process::exit(1);
+        }
+    }
+}
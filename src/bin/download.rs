@@ -2,7 +2,8 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::aoc_cli;
+use advent_of_code::{aoc_cli, ANSI_RED, ANSI_RESET};
+use std::error::Error;
 use std::process;
 
 struct Args {
@@ -27,20 +28,11 @@ fn main() {
         }
     };
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    match aoc_cli::download(args.day, args.year) {
-        Ok(cmd_output) => {
-            if !cmd_output.status.success() {
-                process::exit(1);
-            }
-        }
-        Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {e}");
-            process::exit(1);
+    if let Err(e) = aoc_cli::download(args.day, args.year) {
+        eprintln!("{ANSI_RED}error:{ANSI_RESET} {e}");
+        if let Some(cause) = e.source() {
+            eprintln!("caused by: {cause}");
         }
+        process::exit(1);
     }
 }
@@ -0,0 +1,301 @@
+/*
+ * Turns the code blocks of a puzzle description into example files and example tests.
+ * example: `cargo extract 8`
+ * Asks which part every code block in `src/puzzles/NN.md` is an example for and what it gives,
+ * then writes `src/examples/NN.txt`, `NNb.txt`, ... with their answers in `.answers` files next to them,
+ * and fills in the example tests that `cargo scaffold` left in `src/days/dayNN.rs`.
+ */
+use advent_of_code::puzzle::{self, Example};
+use advent_of_code::{manifest_path, ANSI_BOLD, ANSI_RED, ANSI_RESET};
+use std::io::{stdin, stdout, Write};
+use std::{fs, process};
+
+/// Lines of an example to show before leaving out the rest
+const PREVIEW_LINES: usize = 20;
+
+/// The part an example is for and the answer it gives, if known
+type Answer = (u8, Option<String>);
+
+struct Args {
+    day: u8,
+    force: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        force: args.contains(["-f", "--force"]),
+        day: args.free_from_str()?,
+    })
+}
+
+fn fail(message: impl std::fmt::Display) -> ! {
+    eprintln!("{ANSI_RED}error:{ANSI_RESET} {message}");
+    process::exit(1);
+}
+
+/// The trimmed line typed in, `None` once stdin is closed
+fn prompt(question: &str) -> Option<String> {
+    print!("{question}");
+    stdout().flush().ok()?;
+    let mut line = String::new();
+    match stdin().read_line(&mut line) {
+        Ok(0) | Err(_) => {
+            println!();
+            None
+        }
+        Ok(_) => Some(line.trim().to_string()),
+    }
+}
+
+fn pick_parts() -> Vec<u8> {
+    loop {
+        match prompt("Example for part [1, 2, b for both, Enter to skip]: ").as_deref() {
+            None | Some("") => return vec![],
+            Some("1") => return vec![1],
+            Some("2") => return vec![2],
+            Some("b") => return vec![1, 2],
+            Some(_) => continue,
+        }
+    }
+}
+
+fn pick_answer(part: u8, example: &Example) -> Option<String> {
+    let default = example.answers.first();
+    let question = match default {
+        Some(default) => format!("Answer for part {part} [{default}]: "),
+        None => format!("Answer for part {part} [Enter if unknown]: "),
+    };
+    match prompt(&question) {
+        Some(answer) if !answer.is_empty() => Some(answer),
+        _ => default.cloned(),
+    }
+}
+
+/// `03`, `03b`, `03c`, ...
+fn example_name(day: u8, index: usize) -> String {
+    match index {
+        0 => format!("{day:02}"),
+        _ => format!("{day:02}{}", (b'a' + index as u8) as char),
+    }
+}
+
+fn read_statement(day: u8, name: &str) -> String {
+    if name == example_name(day, 0) {
+        format!("crate::read_file(\"examples\", {day})")
+    } else {
+        format!("crate::read_example(\"{name}\")")
+    }
+}
+
+/// The solver's return type as written in the module, like `Option<u32>`
+fn return_type(module: &str, solver: &str) -> Option<String> {
+    let signature = format!("pub fn {solver}(input: &str) -> ");
+    let start = module.find(&signature)? + signature.len();
+    let end = start + module[start..].find(" {")?;
+    Some(module[start..end].trim().to_string())
+}
+
+/// The answer as a value of the type the solver gives it in
+fn literal(answer_type: &str, answer: &str) -> String {
+    match answer_type {
+        "String" => format!("{answer:?}.to_string()"),
+        "&str" | "&'static str" => format!("{answer:?}"),
+        _ if answer.parse::<i64>().is_ok() => answer.to_string(),
+        _ => format!("{answer:?}.to_string()"),
+    }
+}
+
+/// Both sides of the assertion that the solver gives the answer, shaped like its return type.
+/// Solvers are assumed to return an `Option` as scaffolded, unless the module says otherwise.
+fn assertion(solver: &str, returns: Option<&str>, answer: Option<&str>) -> (String, String) {
+    let call = format!("{solver}(&input)");
+    let returns = returns.unwrap_or("Option<_>");
+    let (result, returns) = match returns
+        .strip_prefix("Result<")
+        .and_then(|inner| inner.strip_suffix('>')?.rsplit_once(", "))
+    {
+        Some((ok, _)) => (true, ok),
+        None => (false, returns),
+    };
+    let option = returns
+        .strip_prefix("Option<")
+        .and_then(|inner| inner.strip_suffix('>'));
+    let expected = match (option, answer) {
+        (Some(answer_type), Some(answer)) => format!("Some({})", literal(answer_type, answer)),
+        (Some(_), None) => "None".to_string(),
+        (None, Some(answer)) => literal(returns, answer),
+        // Only `Solution` can tell that a solver without an `Option` has no answer
+        (None, None) => {
+            return (
+                format!("crate::Solution::answer({call})"),
+                "Ok(None)".to_string(),
+            )
+        }
+    };
+    match result {
+        true => (call, format!("Ok({expected})")),
+        false => (call, expected),
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            process::exit(1);
+        }
+    };
+    let day = args.day;
+    let puzzle_path = format!("src/puzzles/{day:02}.md");
+    let markdown = fs::read_to_string(manifest_path(&puzzle_path)).unwrap_or_else(|e| {
+        fail(format!(
+            "could not read \"{puzzle_path}\": {e}. Run `cargo download {day}` first."
+        ))
+    });
+    let examples = puzzle::examples(&markdown);
+    if examples.is_empty() {
+        fail(format!("there are no code blocks in \"{puzzle_path}\""));
+    }
+
+    let mut picked: Vec<(&Example, Vec<Answer>)> = vec![];
+    for (i, example) in examples.iter().enumerate() {
+        println!(
+            "{ANSI_BOLD}Code block {} of {}, in part {}:{ANSI_RESET}",
+            i + 1,
+            examples.len(),
+            example.part
+        );
+        let lines: Vec<_> = example.input.lines().collect();
+        for line in lines.iter().take(PREVIEW_LINES) {
+            println!("    {line}");
+        }
+        if lines.len() > PREVIEW_LINES {
+            println!("    <{} more lines>", lines.len() - PREVIEW_LINES);
+        }
+        if !example.answers.is_empty() {
+            println!("Answers mentioned after it: {}", example.answers.join(", "));
+        }
+        let parts: Vec<_> = pick_parts()
+            .into_iter()
+            .map(|part| (part, pick_answer(part, example)))
+            .collect();
+        if !parts.is_empty() {
+            picked.push((example, parts));
+        }
+        println!();
+    }
+    if picked.is_empty() {
+        println!("No examples picked.");
+        return;
+    }
+
+    let names: Vec<_> = (0..picked.len()).map(|i| example_name(day, i)).collect();
+    for name in &names {
+        let path = format!("src/examples/{name}.txt");
+        let existing = fs::read_to_string(manifest_path(&path)).unwrap_or_default();
+        if !existing.trim().is_empty() && !args.force {
+            fail(format!(
+                "\"{path}\" already exists, pass --force to overwrite it"
+            ));
+        }
+    }
+    for (name, (example, parts)) in names.iter().zip(&picked) {
+        let path = format!("src/examples/{name}.txt");
+        if let Err(e) = fs::write(manifest_path(&path), &example.input) {
+            fail(format!("could not write \"{path}\": {e}"));
+        }
+        println!("Created example file \"{path}\"");
+        let answers: String = parts
+            .iter()
+            .filter_map(|(part, answer)| Some(format!("{part}: {}\n", answer.as_ref()?)))
+            .collect();
+        if !answers.is_empty() {
+            let path = format!("src/examples/{name}.answers");
+            if let Err(e) = fs::write(manifest_path(&path), answers) {
+                fail(format!("could not write \"{path}\": {e}"));
+            }
+            println!("Created answers file \"{path}\"");
+        }
+    }
+
+    let module_path = format!("src/days/day{day:02}.rs");
+    let Ok(mut module) = fs::read_to_string(manifest_path(&module_path)) else {
+        return;
+    };
+    for (part, solver) in [(1, "part_one"), (2, "part_two")] {
+        let returns = return_type(&module, solver);
+        let body: String = names
+            .iter()
+            .zip(&picked)
+            .filter_map(|(name, (_, parts))| {
+                let (_, answer) = parts.iter().find(|(p, _)| *p == part)?;
+                let (actual, expected) = assertion(solver, returns.as_deref(), answer.as_deref());
+                Some(format!(
+                    "        let input = {};\n        assert_eq!({actual}, {expected});\n",
+                    read_statement(day, name),
+                ))
+            })
+            .collect();
+        if body.is_empty() {
+            continue;
+        }
+        let placeholder = format!(
+            "        let input = crate::read_file(\"examples\", {day});\n        assert_eq!({solver}(&input), None);\n"
+        );
+        if module.contains(&placeholder) {
+            module = module.replacen(&placeholder, &body, 1);
+            println!("Filled in the test of {solver} in \"{module_path}\"");
+        } else {
+            println!("Add to the test of {solver} in \"{module_path}\":\n{body}");
+        }
+    }
+    if let Err(e) = fs::write(manifest_path(&module_path), module) {
+        fail(format!("could not write \"{module_path}\": {e}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assertion() {
+        let module = "pub fn part_one(input: &str) -> Result<Option<u32>, ParseError> {\n}\n\
+                      pub fn part_two(input: &str) -> String {\n}\n";
+        let returns = return_type(module, "part_one");
+        assert_eq!(returns.as_deref(), Some("Result<Option<u32>, ParseError>"));
+        assert_eq!(
+            assertion("part_one", returns.as_deref(), Some("3")),
+            ("part_one(&input)".to_string(), "Ok(Some(3))".to_string())
+        );
+
+        let returns = return_type(module, "part_two");
+        assert_eq!(
+            assertion("part_two", returns.as_deref(), Some("11")),
+            (
+                "part_two(&input)".to_string(),
+                "\"11\".to_string()".to_string()
+            )
+        );
+        assert_eq!(
+            assertion("part_two", returns.as_deref(), None),
+            (
+                "crate::Solution::answer(part_two(&input))".to_string(),
+                "Ok(None)".to_string()
+            )
+        );
+        assert_eq!(
+            assertion("part_one", Some("Result<usize, ParseError>"), Some("7")),
+            ("part_one(&input)".to_string(), "Ok(7)".to_string())
+        );
+        assert_eq!(
+            assertion("part_one", None, Some("easter")),
+            (
+                "part_one(&input)".to_string(),
+                "Some(\"easter\".to_string())".to_string()
+            )
+        );
+    }
+}
@@ -2,12 +2,14 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::aoc_cli;
+use advent_of_code::{aoc_cli, ANSI_RED, ANSI_RESET};
+use std::error::Error;
 use std::process;
 
 struct Args {
     day: u8,
     year: Option<u16>,
+    part: Option<u8>,
 }
 
 fn parse_args() -> Result<Args, pico_args::Error> {
@@ -15,6 +17,10 @@ fn parse_args() -> Result<Args, pico_args::Error> {
     Ok(Args {
         day: args.free_from_str()?,
         year: args.opt_value_from_str(["-y", "--year"])?,
+        part: args.opt_value_from_fn(["-p", "--part"], |part| match part {
+            "1" | "2" => Ok(part.parse::<u8>().unwrap()),
+            _ => Err("expected 1 or 2"),
+        })?,
     })
 }
 
@@ -27,20 +33,11 @@ fn main() {
         }
     };
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    match aoc_cli::read(args.day, args.year) {
-        Ok(cmd_output) => {
-            if !cmd_output.status.success() {
-                process::exit(1);
-            }
-        }
-        Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {e}");
-            process::exit(1);
+    if let Err(e) = aoc_cli::read(args.day, args.year, args.part) {
+        eprintln!("{ANSI_RED}error:{ANSI_RESET} {e}");
+        if let Some(cause) = e.source() {
+            eprintln!("caused by: {cause}");
         }
+        process::exit(1);
     }
 }
@@ -16,30 +16,29 @@ pub fn part_two(input: &str) -> Option<u32> {
     None
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", DAY);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", DAY);
+        let input = crate::read_file("examples", DAY);
         assert_eq!(part_one(&input), None);
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", DAY);
+        let input = crate::read_file("examples", DAY);
         assert_eq!(part_two(&input), None);
     }
 }
 "#;
 
+const BIN_TEMPLATE: &str = r#"fn main() {
+    advent_of_code::days::solve(DAY);
+}
+"#;
+
 fn parse_args() -> Result<u8, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
     args.free_from_str()
@@ -50,7 +49,11 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
 fn main() {
@@ -66,23 +69,26 @@ fn main() {
 
     let input_path = format!("src/inputs/{day_padded}.txt");
     let example_path = format!("src/examples/{day_padded}.txt");
-    let module_path = format!("src/bin/{day_padded}.rs");
-
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
-
-    match file.write_all(MODULE_TEMPLATE.replace("DAY", &day.to_string()).as_bytes()) {
-        Ok(_) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+    let module_path = format!("src/days/day{day_padded}.rs");
+    let bin_path = format!("src/bin/{day_padded}.rs");
+
+    for (path, template) in [(&module_path, MODULE_TEMPLATE), (&bin_path, BIN_TEMPLATE)] {
+        let mut file = match safe_create_file(path) {
+            Ok(file) => file,
+            Err(e) => {
+                eprintln!("Failed to create module file: {e}");
+                process::exit(1);
+            }
+        };
+
+        match file.write_all(template.replace("DAY", &day.to_string()).as_bytes()) {
+            Ok(_) => {
+                println!("Created module file \"{}\"", path);
+            }
+            Err(e) => {
+                eprintln!("Failed to write module contents: {e}");
+                process::exit(1);
+            }
         }
     }
 
@@ -107,6 +113,12 @@ fn main() {
     }
 
     println!("---");
+    println!(
+        "🎄 Add `pub mod day{day_padded};` and `day!({day}, day{day_padded}),` to \"src/days.rs\"."
+    );
+    println!(
+        "🎄 Type `cargo extract {day}` to fill in the example and its tests once the puzzle is downloaded."
+    );
     println!(
         "🎄 Type `cargo solve {}` to run your solution.",
         &day_padded
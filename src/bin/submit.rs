@@ -0,0 +1,139 @@
+/*
+ * Submits an answer, or else the answer the solution gives, and keeps track of every guess.
+ * Answers for other years than this one have to be passed.
+ * example: `cargo submit 1 2 257` or `cargo submit 1 2`
+ * Answers that were wrong before, or that contradict earlier guesses being too high or too low,
+ * are refused unless `--force` is given. Guesses are kept in `guesses.txt`.
+ */
+use advent_of_code::aoc_cli::{AocCliError, Client};
+use advent_of_code::guesses::{Feedback, Guess, Guesses, GUESSES_PATH};
+use advent_of_code::{answers, days, manifest_path, read_input, InputSource, Outcome};
+use advent_of_code::{ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET, YEAR};
+use std::error::Error;
+use std::process;
+
+struct Args {
+    day: u8,
+    part: u8,
+    answer: Option<String>,
+    year: Option<u16>,
+    force: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        year: args.opt_value_from_str(["-y", "--year"])?,
+        force: args.contains(["-f", "--force"]),
+        day: args.free_from_str()?,
+        part: args.free_from_str()?,
+        answer: args.opt_free_from_str()?,
+    })
+}
+
+fn fail(message: impl std::fmt::Display) -> ! {
+    eprintln!("{ANSI_RED}error:{ANSI_RESET} {message}");
+    process::exit(1);
+}
+
+/// Failed requests also tell what the website answered or what went wrong underneath
+fn fail_request(e: AocCliError) -> ! {
+    eprintln!("{ANSI_RED}error:{ANSI_RESET} {e}");
+    if let Some(cause) = e.source() {
+        eprintln!("caused by: {cause}");
+    }
+    process::exit(1);
+}
+
+/// The answer the first solver of the part gives for the default input
+fn solve(day: u8, part: u8) -> String {
+    let Some(solver) = days::get(day).and_then(|d| d.parts.iter().find(|p| p.part == part)) else {
+        fail(format!(
+            "day {day} part {part} is not solved yet, pass the answer instead"
+        ));
+    };
+    let input = read_input(day, &InputSource::Default).unwrap_or_else(|e| fail(e));
+    match (solver.run)(&input) {
+        (Outcome::Solved(answer), _) => answer,
+        (Outcome::Failed(e) | Outcome::Panicked(e), _) => {
+            fail(format!("day {day} part {part} failed: {e}"))
+        }
+        _ => fail(format!(
+            "day {day} part {part} is not solved yet, pass the answer instead"
+        )),
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            process::exit(1);
+        }
+    };
+    let year = args.year.unwrap_or(YEAR);
+    let answer = match args.answer {
+        Some(answer) => answer,
+        // The solutions only solve this year's puzzles
+        None if year != YEAR => fail(format!(
+            "only {YEAR} is solved here, pass the answer for {year} instead"
+        )),
+        None => solve(args.day, args.part),
+    };
+
+    let path = manifest_path(GUESSES_PATH);
+    let mut guesses = Guesses::load(&path)
+        .unwrap_or_else(|e| fail(format!("could not read \"{GUESSES_PATH}\": {e}")));
+    if !args.force {
+        if let Err(refusal) = guesses.check(year, args.day, args.part, &answer) {
+            fail(format!(
+                "not submitting {answer}: {refusal}. Pass --force to submit it anyway."
+            ));
+        }
+    }
+
+    let client = Client::from_env().unwrap_or_else(|e| fail_request(e));
+    println!(
+        "Submitting {ANSI_BOLD}{answer}{ANSI_RESET} for day {}, part {}, {year}...",
+        args.day, args.part
+    );
+    let (feedback, message) = client
+        .submit(year, args.day, args.part, &answer)
+        .unwrap_or_else(|e| fail_request(e));
+    println!("{message}");
+
+    guesses.push(Guess {
+        year,
+        day: args.day,
+        part: args.part,
+        answer: answer.clone(),
+        feedback,
+    });
+    if let Err(e) = guesses.save(&path) {
+        fail(format!("could not write \"{GUESSES_PATH}\": {e}"));
+    }
+
+    match feedback {
+        Feedback::Correct => {
+            println!("{ANSI_GREEN}🎄 {answer} is correct.{ANSI_RESET}");
+            if year == YEAR {
+                let mut recorded = answers::load();
+                recorded.insert(args.day, args.part, &answer);
+                if let Err(e) = recorded.save(&manifest_path(answers::ANSWERS_PATH)) {
+                    fail(format!(
+                        "could not write \"{}\": {e}",
+                        answers::ANSWERS_PATH
+                    ));
+                }
+            }
+        }
+        feedback => {
+            println!(
+                "{ANSI_RED}{answer} was not accepted: {}.{ANSI_RESET}",
+                feedback.to_string().replace('_', " ")
+            );
+            process::exit(1);
+        }
+    }
+}
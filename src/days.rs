@@ -0,0 +1,377 @@
+/*
+ * Every day's solutions, registered so that the aggregate runner can call them in-process.
+ * The binaries in `src/bin` are thin wrappers around `solve`.
+ */
+use crate::answers;
+use crate::baseline::{self, DEFAULT_THRESHOLD};
+use crate::bench::bench;
+use crate::report::{Format, Record, Reporter};
+use crate::{read_input, run_part, run_with_timeout, InputSource, Outcome, ANSI_RED, ANSI_RESET};
+use std::ops::RangeInclusive;
+use std::process;
+use std::str::FromStr;
+use std::time::Duration;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+/// One solver for a part of a puzzle
+pub struct Part {
+    pub part: u8,
+    /// Tells apart several solvers for the same part
+    pub name: &'static str,
+    pub run: fn(&str) -> (Outcome, Duration),
+}
+
+pub struct Day {
+    pub day: u8,
+    pub parts: &'static [Part],
+    /// Takes seconds even in release mode, see `--skip-slow`
+    pub slow: bool,
+}
+
+impl Day {
+    /// The parts picked by `--part`
+    pub fn selected<'a>(&'a self, options: &'a Options) -> impl Iterator<Item = &'a Part> + 'a {
+        self.parts
+            .iter()
+            .filter(|part| options.part.is_none_or(|selected| part.part == selected))
+    }
+
+    /// Runs the selected solvers in order, benchmarking them if asked to
+    pub fn run(&self, input: &str, options: &Options) -> Vec<Record> {
+        self.run_parts(self.selected(options), input, options)
+    }
+
+    pub fn run_parts<'a>(
+        &self,
+        parts: impl IntoIterator<Item = &'a Part>,
+        input: &str,
+        options: &Options,
+    ) -> Vec<Record> {
+        parts
+            .into_iter()
+            .map(|part| {
+                let run = |input: &str| match options.timeout {
+                    Some(timeout) => run_with_timeout(part.run, input, timeout),
+                    None => (part.run)(input),
+                };
+                let (outcome, elapsed, stats) = match options.bench {
+                    Some(runs) => {
+                        let (outcome, stats) = bench(run, input, runs);
+                        let median = stats.map_or(Duration::ZERO, |stats| stats.median);
+                        (outcome, median, stats)
+                    }
+                    None => {
+                        let (outcome, elapsed) = run(input);
+                        (outcome, elapsed, None)
+                    }
+                };
+                Record {
+                    day: self.day,
+                    part: part.part,
+                    solver: part.name,
+                    outcome,
+                    elapsed,
+                    stats,
+                    expected: None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Flags shared by `cargo solve` and `cargo all`
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub format: Format,
+    /// Timed runs per part, after some warmup runs
+    pub bench: Option<usize>,
+    /// Writes the timings to the baseline
+    pub save_baseline: bool,
+    /// Compares the timings to the baseline, exiting with 1 on regressions
+    pub compare: bool,
+    /// Percent a part may get slower than its baseline
+    pub threshold: f64,
+    /// Saves the answers as known-good
+    pub record: bool,
+    /// Exits with 1 when a recorded answer changed
+    pub verify: bool,
+    /// Only solve this part
+    pub part: Option<u8>,
+    /// How long a part may take before it is given up on
+    pub timeout: Option<Duration>,
+    pub input: InputSource,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            format: Format::default(),
+            bench: None,
+            save_baseline: false,
+            compare: false,
+            threshold: DEFAULT_THRESHOLD,
+            record: false,
+            verify: false,
+            part: None,
+            timeout: None,
+            input: InputSource::Default,
+        }
+    }
+}
+
+impl Options {
+    /// Answers and timings are only recorded for `src/inputs`, so flags that
+    /// record or compare them can't be combined with `--input`
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Options, pico_args::Error> {
+        let options = Options {
+            format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+            bench: args.opt_value_from_str("--bench")?,
+            save_baseline: args.contains("--save-baseline"),
+            compare: args.contains("--compare"),
+            threshold: args
+                .opt_value_from_str("--threshold")?
+                .unwrap_or(DEFAULT_THRESHOLD),
+            record: args.contains("--record"),
+            verify: args.contains("--verify"),
+            part: args.opt_value_from_str("--part")?,
+            timeout: args.opt_value_from_fn("--timeout", |seconds| {
+                Duration::try_from_secs_f64(seconds.parse().map_err(|_| "expected seconds")?)
+                    .map_err(|_| "expected a positive number of seconds")
+            })?,
+            input: args.opt_value_from_str("--input")?.unwrap_or_default(),
+        };
+        let tracking = options.record || options.verify || options.save_baseline || options.compare;
+        if tracking && options.input != InputSource::Default {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "--input can't be combined with --record, --verify, --save-baseline or --compare".to_string(),
+            });
+        }
+        Ok(options)
+    }
+
+    /// Exits with the error when the flags can't be parsed, or there are any others
+    pub fn from_env() -> Options {
+        let mut args = pico_args::Arguments::from_env();
+        let options =
+            Options::from_args(&mut args).and_then(|options| match args.finish().as_slice() {
+                [] => Ok(options),
+                unused => Err(pico_args::Error::ArgumentParsingFailed {
+                    cause: format!("unexpected arguments {unused:?}"),
+                }),
+            });
+        options.unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        })
+    }
+}
+
+/// Days picked on the command line, like `5-9,14`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Selection(Vec<RangeInclusive<u8>>);
+
+impl Selection {
+    pub fn contains(&self, day: u8) -> bool {
+        self.0.iter().any(|days| days.contains(&day))
+    }
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let day = |day: &str| match day.trim().parse::<u8>() {
+            Ok(day @ 1..=25) => Ok(day),
+            Ok(day) => Err(format!("day {day} is not one of the days 1 to 25")),
+            Err(_) => Err(format!(
+                "expected a day like `5`, `5-9` or `5-9,14`, got \"{s}\""
+            )),
+        };
+        s.split(',')
+            .map(|days| match days.split_once('-') {
+                Some((first, last)) => match (day(first)?, day(last)?) {
+                    (first, last) if first <= last => Ok(first..=last),
+                    _ => Err(format!("\"{days}\" is an empty range of days")),
+                },
+                None => day(days).map(|day| day..=day),
+            })
+            .collect::<Result<_, _>>()
+            .map(Selection)
+    }
+}
+
+macro_rules! part {
+    ($part:expr, $module:ident::$solver:ident) => {
+        Part {
+            part: $part,
+            name: stringify!($solver),
+            run: |input| run_part($module::$solver, input),
+        }
+    };
+}
+
+macro_rules! day {
+    ($day:expr, $module:ident) => {
+        day!($day, $module, [(1, part_one), (2, part_two)])
+    };
+    ($day:expr, $module:ident, slow) => {
+        Day {
+            slow: true,
+            ..day!($day, $module)
+        }
+    };
+    ($day:expr, $module:ident, [$(($part:expr, $solver:ident)),*]) => {
+        Day {
+            day: $day,
+            parts: &[$(part!($part, $module::$solver)),*],
+            slow: false,
+        }
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    day!(1, day01),
+    day!(2, day02),
+    day!(3, day03),
+    day!(4, day04),
+    day!(5, day05, slow),
+    day!(6, day06),
+    day!(7, day07),
+    day!(8, day08),
+    day!(9, day09),
+    day!(10, day10),
+    day!(11, day11),
+    day!(12, day12),
+    day!(13, day13),
+    day!(14, day14, slow),
+    day!(15, day15),
+    day!(16, day16, slow),
+    day!(17, day17),
+    day!(18, day18),
+    day!(19, day19),
+    day!(20, day20),
+    day!(21, day21),
+    day!(22, day22),
+    day!(23, day23),
+    day!(24, day24),
+    day!(25, day25, [(1, part_one), (1, part_one_fast)]),
+];
+
+pub fn get(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+/// Solves a day against its input and prints every part, as `cargo solve` does
+pub fn solve(day: u8) {
+    let Some(day) = get(day) else {
+        panic!("day {day} is not registered in src/days.rs");
+    };
+    let options = Options::from_env();
+    let answers = answers::load();
+    let input = read_input(day.day, &options.input).unwrap_or_else(|e| {
+        eprintln!("{ANSI_RED}error:{ANSI_RESET} {e}");
+        process::exit(1);
+    });
+    let mut reporter = Reporter::new(options.format);
+    let mut records = day.run(&input, &options);
+    if options.input == InputSource::Default {
+        answers.mark(&mut records);
+    }
+    for record in &records {
+        reporter.record(record);
+    }
+    reporter.finish();
+
+    let regressed = baseline::check(&records, &options);
+    let changed = answers::check(answers, &records, &options);
+    if regressed || changed {
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        assert!(DAYS.iter().map(|day| day.day).eq(1..=25));
+        assert_eq!(get(25).unwrap().parts[1].name, "part_one_fast");
+        assert!(get(26).is_none());
+
+        let day = get(1).unwrap();
+        assert_eq!(
+            (day.parts[1].run)("R8, R4, R4, R8").0,
+            Outcome::Solved("4".to_string())
+        );
+        assert!(day
+            .run("X1", &Options::default())
+            .iter()
+            .all(Record::is_failure));
+
+        let options = Options {
+            bench: Some(5),
+            part: Some(2),
+            ..Options::default()
+        };
+        let records = get(19).unwrap().run("5", &options);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].answer(), Some("2"));
+        assert_eq!(records[0].stats.map(|stats| stats.runs), Some(5));
+    }
+
+    #[test]
+    fn test_options() {
+        let options = |args: &[&str]| {
+            let args = args.iter().map(Into::into).collect();
+            Options::from_args(&mut pico_args::Arguments::from_vec(args))
+        };
+        assert!(options(&["--record"]).unwrap().record);
+        assert_eq!(
+            options(&["--input", "-"]).unwrap().input,
+            InputSource::Stdin
+        );
+        assert!(options(&["--input", "-", "--record"]).is_err());
+        assert!(options(&["--input", "stress.txt", "--compare"]).is_err());
+    }
+
+    #[test]
+    fn test_selection() {
+        let selection: Selection = "5-9, 14".parse().unwrap();
+        assert!((1..=25)
+            .filter(|&day| selection.contains(day))
+            .eq([5, 6, 7, 8, 9, 14]));
+        assert!("9-5".parse::<Selection>().is_err());
+        assert!("5,x".parse::<Selection>().is_err());
+        assert_eq!(
+            "0-3".parse::<Selection>(),
+            Err("day 0 is not one of the days 1 to 25".to_string())
+        );
+        assert!("20-26".parse::<Selection>().is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use crate::helpers::{parse, ParseError};
+use itertools::Itertools;
+use nom::character::complete::{line_ending, multispace0, u32 as u32_nom};
+use nom::multi::separated_list1;
+use nom::sequence::{preceded, tuple};
+use nom::IResult;
+
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let triples = parse(triples, input)?;
+    Ok(triples.iter().filter(|triple| can_triangle(triple)).count())
+}
+
+fn can_triangle((a, b, c): &Triple) -> bool {
+    let ordered: [u32; 3] = vec![a, b, c]
+        .into_iter()
+        .copied()
+        .sorted()
+        .collect_vec()
+        .try_into()
+        .unwrap();
+
+    (ordered[0] + ordered[1]) > ordered[2]
+}
+
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let triples: Vec<Triple> = parse(triples, input)?
+        .chunks(3)
+        .flat_map(|chunk| {
+            let [a, b, c] = chunk else {
+                panic!("Uneven chunks of three")
+            };
+            vec![(a.0, b.0, c.0), (a.1, b.1, c.1), (a.2, b.2, c.2)]
+        })
+        .collect_vec();
+    Ok(triples.iter().filter(|triple| can_triangle(triple)).count())
+}
+
+type Triple = (u32, u32, u32);
+fn triples(input: &str) -> IResult<&str, Vec<Triple>> {
+    separated_list1(line_ending, triple)(input)
+}
+fn triple(input: &str) -> IResult<&str, Triple> {
+    tuple((triple_entry, triple_entry, triple_entry))(input)
+}
+fn triple_entry(input: &str) -> IResult<&str, u32> {
+    preceded(multispace0, u32_nom)(input)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 3);
+        assert_eq!(part_one(&input), Ok(2));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_example("03b");
+        assert_eq!(part_two(&input), Ok(6));
+    }
+}
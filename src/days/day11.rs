@@ -0,0 +1,301 @@
+use self::Item::{Generator, Microchip};
+use crate::helpers::{parse, ParseError};
+use itertools::Itertools;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{alpha1, line_ending};
+use nom::combinator::{map, value};
+use nom::multi::{separated_list0, separated_list1};
+use nom::sequence::{delimited, preceded, terminated};
+use nom::IResult;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+pub fn part_one(input: &str) -> Result<Option<usize>, ParseError> {
+    let floors = parse(floors, input)?;
+    let state = State::from(&floors).map_err(|item| unpaired(input, item))?;
+    Ok(state.fewest_steps())
+}
+
+/// An elerium generator, an elerium-compatible microchip, a dilithium generator
+/// and a dilithium-compatible microchip are also waiting on the first floor.
+pub fn part_two(input: &str) -> Result<Option<usize>, ParseError> {
+    let mut floors = parse(floors, input)?;
+    floors[0].extend([
+        Generator("elerium".to_string()),
+        Microchip("elerium".to_string()),
+        Generator("dilithium".to_string()),
+        Microchip("dilithium".to_string()),
+    ]);
+    let state = State::from(&floors).map_err(|item| unpaired(input, item))?;
+    Ok(state.fewest_steps())
+}
+
+const TOP_FLOOR: u8 = 3;
+
+/// Points at the item in `input` that is missing its generator or microchip
+fn unpaired(input: &str, item: &Item) -> ParseError {
+    let (text, missing) = match item {
+        Generator(element) => (format!("{element} generator"), "microchip"),
+        Microchip(element) => (format!("{element}-compatible microchip"), "generator"),
+    };
+    let input = input.trim_end();
+    let rest = input.find(&text).map_or("", |idx| &input[idx..]);
+    ParseError::at(input, rest, format!("{text} has no {missing}"))
+}
+
+/// Only the floors of each generator/microchip pair matter, not which element they are.
+/// Keeping the pairs sorted makes every state that differs only by element names
+/// collapse into the same canonical state, which is what keeps part two tractable.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+struct State {
+    elevator: u8,
+    /// `(generator floor, microchip floor)` for each element
+    pairs: Vec<(u8, u8)>,
+}
+
+impl State {
+    /// Fails with the first item whose generator or microchip is nowhere to be found
+    fn from(floors: &[Vec<Item>]) -> Result<Self, &Item> {
+        let mut generators: HashMap<&str, (u8, &Item)> = HashMap::new();
+        let mut microchips: HashMap<&str, (u8, &Item)> = HashMap::new();
+        for (floor, items) in floors.iter().enumerate() {
+            for item in items {
+                match item {
+                    Generator(element) => generators.insert(element, (floor as u8, item)),
+                    Microchip(element) => microchips.insert(element, (floor as u8, item)),
+                };
+            }
+        }
+        if let Some((_, &(_, item))) = microchips
+            .iter()
+            .find(|(element, _)| !generators.contains_key(*element))
+        {
+            return Err(item);
+        }
+        let pairs = generators
+            .iter()
+            .map(|(element, &(generator, item))| {
+                microchips
+                    .get(element)
+                    .map(|&(microchip, _)| (generator, microchip))
+                    .ok_or(item)
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .sorted()
+            .collect_vec();
+        Ok(Self { elevator: 0, pairs })
+    }
+
+    /// Breadth first search over canonical states, each elevator stop is one step
+    fn fewest_steps(&self) -> Option<usize> {
+        if !self.is_safe() {
+            return None;
+        }
+        let mut seen = HashSet::from([self.clone()]);
+        let mut queue = VecDeque::from([(self.clone(), 0usize)]);
+
+        while let Some((state, steps)) = queue.pop_front() {
+            if state.is_complete() {
+                return Some(steps);
+            }
+            for next in state.next_states() {
+                if seen.insert(next.clone()) {
+                    queue.push_back((next, steps + 1));
+                }
+            }
+        }
+        None
+    }
+
+    fn is_complete(&self) -> bool {
+        self.pairs
+            .iter()
+            .all(|&(generator, microchip)| generator == TOP_FLOOR && microchip == TOP_FLOOR)
+    }
+
+    /// A microchip is fried when it shares a floor with another generator
+    /// without being connected to its own.
+    fn is_safe(&self) -> bool {
+        self.pairs.iter().all(|&(generator, microchip)| {
+            generator == microchip || !self.pairs.iter().any(|&(other, _)| other == microchip)
+        })
+    }
+
+    /// Items on the elevator's floor as `(pair index, is generator)`
+    fn items_on_floor(&self) -> Vec<(usize, bool)> {
+        self.pairs
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, &(generator, microchip))| {
+                [
+                    (generator == self.elevator).then_some((idx, true)),
+                    (microchip == self.elevator).then_some((idx, false)),
+                ]
+            })
+            .flatten()
+            .collect_vec()
+    }
+
+    fn next_states(&self) -> Vec<State> {
+        let items = self.items_on_floor();
+        let cargo = items
+            .iter()
+            .map(|item| vec![*item])
+            .chain(
+                items
+                    .iter()
+                    .tuple_combinations()
+                    .map(|(first, second)| vec![*first, *second]),
+            )
+            .collect_vec();
+
+        let floors = [
+            self.elevator.checked_sub(1),
+            (self.elevator < TOP_FLOOR).then_some(self.elevator + 1),
+        ];
+
+        floors
+            .into_iter()
+            .flatten()
+            // Never bring anything back down to floors that are already empty
+            .filter(|&floor| {
+                floor > self.elevator
+                    || self
+                        .pairs
+                        .iter()
+                        .any(|&(generator, microchip)| generator <= floor || microchip <= floor)
+            })
+            .flat_map(|floor| cargo.iter().map(move |load| self.carry(load, floor)))
+            .filter(State::is_safe)
+            .unique()
+            .collect_vec()
+    }
+
+    fn carry(&self, load: &[(usize, bool)], floor: u8) -> State {
+        let mut pairs = self.pairs.clone();
+        for &(idx, is_generator) in load {
+            if is_generator {
+                pairs[idx].0 = floor;
+            } else {
+                pairs[idx].1 = floor;
+            }
+        }
+        pairs.sort();
+        State {
+            elevator: floor,
+            pairs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Item {
+    Generator(String),
+    Microchip(String),
+}
+
+fn floors(input: &str) -> IResult<&str, Vec<Vec<Item>>> {
+    separated_list1(line_ending, floor)(input)
+}
+fn floor(input: &str) -> IResult<&str, Vec<Item>> {
+    delimited(
+        terminated(preceded(tag("The "), alpha1), tag(" floor contains ")),
+        alt((value(vec![], tag("nothing relevant")), items)),
+        tag("."),
+    )(input)
+}
+fn items(input: &str) -> IResult<&str, Vec<Item>> {
+    separated_list0(alt((tag(", and "), tag(", "), tag(" and "))), item)(input)
+}
+fn item(input: &str) -> IResult<&str, Item> {
+    preceded(tag("a "), alt((microchip, generator)))(input)
+}
+fn microchip(input: &str) -> IResult<&str, Item> {
+    map(
+        terminated(alpha1, tag("-compatible microchip")),
+        |element| Microchip(String::from(element)),
+    )(input)
+}
+fn generator(input: &str) -> IResult<&str, Item> {
+    map(terminated(alpha1, tag(" generator")), |element| {
+        Generator(String::from(element))
+    })(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_floors() {
+        let input = crate::read_file("examples", 11);
+        let (remaining, floors) = floors(input.trim()).unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(
+            floors,
+            vec![
+                vec![
+                    Microchip("hydrogen".to_string()),
+                    Microchip("lithium".to_string())
+                ],
+                vec![Generator("hydrogen".to_string())],
+                vec![Generator("lithium".to_string())],
+                vec![],
+            ]
+        );
+        assert_eq!(
+            floor("The first floor contains a thulium generator, a thulium-compatible microchip, a plutonium generator, and a strontium generator."),
+            Ok((
+                "",
+                vec![
+                    Generator("thulium".to_string()),
+                    Microchip("thulium".to_string()),
+                    Generator("plutonium".to_string()),
+                    Generator("strontium".to_string()),
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 11);
+        assert_eq!(part_one(&input), Ok(Some(11)));
+    }
+
+    #[test]
+    fn test_part_two() {
+        // The extra generators fry the example's microchips before the elevator ever moves
+        let input = crate::read_file("examples", 11);
+        assert_eq!(part_two(&input), Ok(None));
+
+        let input = "\
+The first floor contains a hydrogen generator and a hydrogen-compatible microchip.
+The second floor contains nothing relevant.
+The third floor contains nothing relevant.
+The fourth floor contains nothing relevant.";
+        assert_eq!(part_one(input), Ok(Some(3)));
+        assert_eq!(part_two(input), Ok(Some(27)));
+    }
+
+    #[test]
+    fn test_unpaired() {
+        let input = "\
+The first floor contains a hydrogen-compatible microchip.
+The second floor contains a lithium generator.";
+        let error = part_one(input).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 28));
+        assert_eq!(
+            error.message,
+            "hydrogen-compatible microchip has no generator"
+        );
+
+        let input = "\
+The first floor contains a hydrogen-compatible microchip and a hydrogen generator.
+The second floor contains a lithium generator.";
+        let error = part_two(input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 29));
+        assert_eq!(error.message, "lithium generator has no microchip");
+    }
+}
@@ -0,0 +1,32 @@
+use crate::assembunny::{instructions, Computer};
+use crate::helpers::{parse, ParseError};
+
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let mut computer = Computer::optimized(parse(instructions, input)?);
+    computer.run();
+    Ok(computer.get('a') as u32)
+}
+
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let mut computer = Computer::optimized(parse(instructions, input)?);
+    computer.set('c', 1);
+    computer.run();
+    Ok(computer.get('a') as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 12);
+        assert_eq!(part_one(&input), Ok(42));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 12);
+        assert_eq!(part_two(&input), Ok(42));
+    }
+}
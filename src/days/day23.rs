@@ -0,0 +1,33 @@
+use crate::assembunny::{instructions, Computer};
+use crate::helpers::{parse, ParseError};
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    let mut computer = Computer::optimized(parse(instructions, input)?);
+    computer.set('a', 7);
+    computer.run();
+    Ok(computer.get('a'))
+}
+
+pub fn part_two(input: &str) -> Result<i32, ParseError> {
+    let mut computer = Computer::optimized(parse(instructions, input)?);
+    computer.set('a', 12);
+    computer.run();
+    Ok(computer.get('a'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 23);
+        assert_eq!(part_one(&input), Ok(3));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 23);
+        assert_eq!(part_two(&input), Ok(3));
+    }
+}
@@ -0,0 +1,62 @@
+use crate::assembunny::analysis::Analysis;
+use crate::assembunny::Instruction::Out;
+use crate::assembunny::{instructions, Computer, Instruction, Signal};
+use crate::helpers::{cancellable, parse, ParseError};
+
+/// Only a seed whose output is proven to repeat `0, 1, 0, 1, ..` forever will do
+pub fn part_one(input: &str) -> Result<Option<u32>, ParseError> {
+    let instructions = parse(instructions, input)?;
+    Ok(cancellable(0u32..).find(|seed| {
+        let mut computer = Computer::optimized(instructions.clone());
+        computer.set('a', *seed as i32);
+        matches!(computer.clock_signal(MAX_STEPS), Signal::Clock { .. })
+    }))
+}
+
+/// Every seed either breaks the pattern or repeats its state well within this many steps
+const MAX_STEPS: usize = 100_000;
+
+/// No actual Part2 today, but I took the chance to optimize the problem based on my input
+pub fn part_one_fast(input: &str) -> Result<Option<u32>, ParseError> {
+    let instructions = parse(instructions, input)?;
+    Ok(fast_track(&instructions))
+}
+
+fn fast_track(instructions: &[Instruction]) -> Option<u32> {
+    let embedded = extract_embedded_seed(instructions)?;
+    let embedded_binary = format!("{embedded:0b}");
+    let max = embedded_binary.len() + (embedded_binary.len() & 0b1); //Must be even to have a valid repetition
+    let smallest_in_binary: String = (0..=max)
+        .map(|i| match i % 2 {
+            0 => '0',
+            _ => '1',
+        })
+        .collect();
+    Some(u32::from_str_radix(&smallest_in_binary, 2).unwrap() - embedded)
+}
+
+/// The program adds a constant to the seed, then transmits the bits of `a` over and over.
+/// Evaluating everything up to the loop around `out` tells what that constant is.
+fn extract_embedded_seed(instructions: &[Instruction]) -> Option<u32> {
+    let analysis = Analysis::new(instructions);
+    let transmitter = analysis.innermost_loop(|instruction| matches!(instruction, Out(_)))?;
+    let [a, ..] = analysis.evaluate_until(transmitter.header)?;
+    (a.factor == 1).then_some(a.offset as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 25);
+        assert_eq!(part_one(&input), Ok(Some(38)));
+    }
+
+    #[test]
+    fn test_part_one_faster() {
+        let input = crate::read_file("examples", 25);
+        assert_eq!(part_one_fast(&input), Ok(Some(38)));
+    }
+}
@@ -0,0 +1,266 @@
+/*
+ * Every answer submitted so far and what the website said about it, so that no wrong answer
+ * is sent twice and answers outside the bounds learned from earlier guesses aren't sent at all.
+ */
+use crate::answers::{escape, unescape};
+use crate::parse_file_or_default;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+use std::str::FromStr;
+
+pub const GUESSES_PATH: &str = "guesses.txt";
+
+/// What the website said about a submitted answer
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Feedback {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Submitted too soon after the last wrong answer, so it wasn't checked
+    RateLimited,
+    /// The part was already solved or isn't unlocked yet, so it wasn't checked
+    WrongLevel,
+}
+
+impl Feedback {
+    /// Whether the answer is known to be wrong
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Feedback::TooHigh | Feedback::TooLow | Feedback::Wrong)
+    }
+}
+
+impl FromStr for Feedback {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Feedback::Correct),
+            "too_high" => Ok(Feedback::TooHigh),
+            "too_low" => Ok(Feedback::TooLow),
+            "wrong" => Ok(Feedback::Wrong),
+            "rate_limited" => Ok(Feedback::RateLimited),
+            "wrong_level" => Ok(Feedback::WrongLevel),
+            _ => Err(format!("unknown feedback \"{s}\"")),
+        }
+    }
+}
+
+impl Display for Feedback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Feedback::Correct => "correct",
+            Feedback::TooHigh => "too_high",
+            Feedback::TooLow => "too_low",
+            Feedback::Wrong => "wrong",
+            Feedback::RateLimited => "rate_limited",
+            Feedback::WrongLevel => "wrong_level",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Guess {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub feedback: Feedback,
+}
+
+/// Why an answer shouldn't be submitted
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Refusal {
+    Solved(String),
+    KnownWrong(Feedback),
+    NotBelow(String),
+    NotAbove(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::Solved(answer) => write!(f, "the part was already solved with {answer}"),
+            Refusal::KnownWrong(feedback) => {
+                write!(f, "the answer was already submitted and was {feedback}")
+            }
+            Refusal::NotBelow(bound) => write!(f, "the answer must be lower than {bound}"),
+            Refusal::NotAbove(bound) => write!(f, "the answer must be higher than {bound}"),
+        }
+    }
+}
+
+/// Guesses in the order they were submitted, one `2016/01/1 too_high: <answer>` per line.
+/// Answers are escaped like in `answers.txt`.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Guesses {
+    guesses: Vec<Guess>,
+}
+
+impl Guesses {
+    /// No guesses if there is no file yet
+    pub fn load(path: &Path) -> io::Result<Guesses> {
+        parse_file_or_default(path)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn push(&mut self, guess: Guess) {
+        self.guesses.push(guess);
+    }
+
+    /// The guesses for one part, oldest first
+    pub fn of(&self, year: u16, day: u8, part: u8) -> impl Iterator<Item = &Guess> {
+        self.guesses
+            .iter()
+            .filter(move |guess| (guess.year, guess.day, guess.part) == (year, day, part))
+    }
+
+    /// Refuses answers to solved parts, wrong answers and, for numbers, answers outside
+    /// the bounds given by the ones that were too high or too low
+    pub fn check(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<(), Refusal> {
+        let guesses: Vec<_> = self.of(year, day, part).collect();
+        if let Some(correct) = guesses
+            .iter()
+            .find(|guess| guess.feedback == Feedback::Correct)
+        {
+            return Err(Refusal::Solved(correct.answer.clone()));
+        }
+        if let Some(wrong) = guesses
+            .iter()
+            .find(|guess| guess.answer == answer && guess.feedback.is_wrong())
+        {
+            return Err(Refusal::KnownWrong(wrong.feedback));
+        }
+
+        let Ok(answer) = answer.parse::<i64>() else {
+            return Ok(());
+        };
+        let bound = |feedback| {
+            guesses
+                .iter()
+                .filter(move |guess| guess.feedback == feedback)
+                .filter_map(|guess| guess.answer.parse::<i64>().ok())
+        };
+        if let Some(high) = bound(Feedback::TooHigh)
+            .min()
+            .filter(|&high| answer >= high)
+        {
+            return Err(Refusal::NotBelow(high.to_string()));
+        }
+        if let Some(low) = bound(Feedback::TooLow).max().filter(|&low| answer <= low) {
+            return Err(Refusal::NotAbove(low.to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Guesses {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |line: &str| {
+            io::Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid guess entry \"{line}\""),
+            )
+        };
+        let guesses = s
+            .lines()
+            .map(|line| {
+                let (key, answer) = line.split_once(": ").ok_or_else(|| invalid(line))?;
+                let (key, feedback) = key.split_once(' ').ok_or_else(|| invalid(line))?;
+                let key: Vec<_> = key.split('/').collect();
+                match key[..] {
+                    [year, day, part] => Ok(Guess {
+                        year: year.parse().map_err(|_| invalid(line))?,
+                        day: day.parse().map_err(|_| invalid(line))?,
+                        part: part.parse().map_err(|_| invalid(line))?,
+                        answer: unescape(answer),
+                        feedback: feedback.parse().map_err(|_| invalid(line))?,
+                    }),
+                    _ => Err(invalid(line)),
+                }
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(Guesses { guesses })
+    }
+}
+
+impl Display for Guesses {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for guess in &self.guesses {
+            writeln!(
+                f,
+                "{}/{:02}/{} {}: {}",
+                guess.year,
+                guess.day,
+                guess.part,
+                guess.feedback,
+                escape(&guess.answer)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guesses() -> Guesses {
+        "2016/01/2 too_high: 257\n\
+         2016/01/2 rate_limited: 100\n\
+         2016/01/2 too_low: 90\n\
+         2016/01/2 wrong: 120\n\
+         2016/09/1 correct: 120766\n"
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut guesses = guesses();
+        guesses.push(Guess {
+            year: 2016,
+            day: 8,
+            part: 2,
+            answer: "#..\n.#.".to_string(),
+            feedback: Feedback::Wrong,
+        });
+        assert!(guesses
+            .to_string()
+            .ends_with("2016/09/1 correct: 120766\n2016/08/2 wrong: #..\\n.#.\n"));
+        assert_eq!(guesses.to_string().parse::<Guesses>().unwrap(), guesses);
+        assert!("2016/01/2: 257".parse::<Guesses>().is_err());
+        assert!("2016/01/2 high: 257".parse::<Guesses>().is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let guesses = guesses();
+        assert_eq!(guesses.check(2016, 1, 2, "100"), Ok(()));
+        assert_eq!(guesses.check(2016, 1, 2, "abc"), Ok(()));
+        assert_eq!(
+            guesses.check(2016, 1, 2, "120"),
+            Err(Refusal::KnownWrong(Feedback::Wrong))
+        );
+        assert_eq!(
+            guesses.check(2016, 1, 2, "300"),
+            Err(Refusal::NotBelow("257".to_string()))
+        );
+        assert_eq!(
+            guesses.check(2016, 1, 2, "80"),
+            Err(Refusal::NotAbove("90".to_string()))
+        );
+        assert_eq!(
+            guesses.check(2016, 9, 1, "1"),
+            Err(Refusal::Solved("120766".to_string()))
+        );
+        assert_eq!(guesses.check(2016, 9, 2, "1"), Ok(()));
+    }
+}
@@ -2,3 +2,169 @@
  * Use this file if you want to extract helpers from your solutions.
  * Example import from this file: `use advent_of_code::helpers::example_fn;`.
  */
+use nom::{Err, IResult};
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Where and why an input couldn't be parsed
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    /// Starting at 1
+    pub line: usize,
+    /// Starting at 1, counted in characters
+    pub column: usize,
+    /// The line that failed to parse
+    pub snippet: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// An error at the start of `rest`, which has to be the end of `input`
+    pub fn at(input: &str, rest: &str, message: String) -> Self {
+        let offset = input.len() - rest.len();
+        let consumed = &input[..offset];
+        let line_start = consumed.rfind('\n').map_or(0, |idx| idx + 1);
+        Self {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+            snippet: input[line_start..].lines().next().unwrap_or("").to_string(),
+            message,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let line = self.line.to_string();
+        let gutter = " ".repeat(line.len());
+        writeln!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{line} | {}", self.snippet)?;
+        write!(f, "{gutter} | {}^", " ".repeat(self.column - 1))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Runs a nom parser over the whole input, trailing whitespace aside.
+/// Input the parser leaves over is an error, just like input it fails on.
+pub fn parse<'a, T>(
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+    input: &'a str,
+) -> Result<T, ParseError> {
+    let input = input.trim_end();
+    match parser(input) {
+        Ok(("", parsed)) => Ok(parsed),
+        // Parsers for lists stop at the first item that fails, so this is where that item starts
+        Ok((remaining, _)) => Err(ParseError::at(
+            input,
+            remaining.trim_start(),
+            "unexpected input".to_string(),
+        )),
+        Err(Err::Error(error) | Err::Failure(error)) => Err(ParseError::at(
+            input,
+            error.input,
+            format!("expected {}", error.code.description().to_lowercase()),
+        )),
+        Err(Err::Incomplete(_)) => Err(ParseError::at(
+            input,
+            "",
+            "unexpected end of input".to_string(),
+        )),
+    }
+}
+
+/// Tells a solver that ran out of time to stop, see `cancelled`
+#[derive(Debug, Clone, Default)]
+pub struct Cancellation(Arc<AtomicBool>);
+
+thread_local! {
+    static CANCELLATION: RefCell<Cancellation> = RefCell::default();
+}
+
+impl Cancellation {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Makes `cancelled` on the current thread follow this token
+    pub fn install(&self) {
+        CANCELLATION.with(|cancellation| *cancellation.borrow_mut() = self.clone());
+    }
+}
+
+/// Whether the part running on this thread was cancelled, for long loops to poll.
+/// What a cancelled solver returns doesn't matter, it is reported as timed out.
+pub fn cancelled() -> bool {
+    CANCELLATION.with(|cancellation| cancellation.borrow().is_cancelled())
+}
+
+/// Stops the iterator once the running part is cancelled, e.g. `cancellable(0..).find(..)`
+pub fn cancellable<I: IntoIterator>(iter: I) -> impl Iterator<Item = I::Item> {
+    iter.into_iter().take_while(|_| !cancelled())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::character::complete::{line_ending, u32 as nom_u32};
+    use nom::multi::separated_list1;
+
+    fn numbers(input: &str) -> IResult<&str, Vec<u32>> {
+        separated_list1(line_ending, nom_u32)(input)
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(numbers, "1\n2\r\n3\n"), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            parse(numbers, "1\n2\nthree\n4"),
+            Err(ParseError {
+                line: 3,
+                column: 1,
+                snippet: "three".to_string(),
+                message: "unexpected input".to_string(),
+            })
+        );
+        assert_eq!(
+            parse(numbers, "1\n22x"),
+            Err(ParseError {
+                line: 2,
+                column: 3,
+                snippet: "22x".to_string(),
+                message: "unexpected input".to_string(),
+            })
+        );
+        assert_eq!(
+            parse(numbers, "x").unwrap_err().to_string(),
+            "expected digit at line 1, column 1\n  |\n1 | x\n  | ^"
+        );
+    }
+
+    #[test]
+    fn test_cancellable() {
+        let cancellation = Cancellation::default();
+        cancellation.install();
+        let mut seen = 0;
+        let found = cancellable(0..).find(|&i| {
+            seen = i;
+            if i == 3 {
+                cancellation.cancel();
+            }
+            false
+        });
+        assert_eq!((found, seen), (None, 3));
+        assert!(cancelled());
+        Cancellation::default().install();
+    }
+}
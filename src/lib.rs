@@ -3,244 +3,353 @@
  * There is no need to edit this file unless you want to change template functionality.
  * Prefer `./helpers.rs` if you want to extract code from your solutions.
  */
-use std::env;
+use helpers::Cancellation;
+use std::any::Any;
+use std::cell::Cell;
+use std::convert::Infallible;
+use std::fmt::{self, Display, Formatter};
 use std::fs;
-
+use std::io::{self, ErrorKind};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Once;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub mod answers;
+pub mod aoc_cli;
+pub mod assembunny;
+pub mod baseline;
+pub mod batch;
+pub mod bench;
+pub mod days;
+pub mod guesses;
 pub mod helpers;
+pub mod puzzle;
+pub mod report;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+
+/// The event these solutions are for
+pub const YEAR: u16 = 2016;
+
+/// Printed in front of errors, panics and timeouts
+pub const FAILED: &str = "error:";
+pub const PANICKED: &str = "panicked:";
+pub const TIMED_OUT: &str = "timed out:";
+
+/// Same as the main thread's, solutions may recurse deeply
+pub const STACK_SIZE: usize = 8 * 1024 * 1024;
 
 #[macro_export]
 macro_rules! solve {
     ($part:expr, $solver:ident, $input:expr) => {{
-        use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-        use std::fmt::Display;
-        use std::time::Instant;
-
-        fn print_result<T: Display>(func: impl FnOnce(&str) -> Option<T>, input: &str) {
-            let timer = Instant::now();
-            let result = func(input);
-            let elapsed = timer.elapsed();
-            match result {
-                Some(result) => {
-                    println!(
-                        "{} {}(elapsed: {:.2?}){}",
-                        result, ANSI_ITALIC, elapsed, ANSI_RESET
-                    );
-                }
-                None => {
-                    println!("not solved.")
-                }
-            }
-        }
-
-        println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
-        print_result($solver, $input);
+        let (outcome, elapsed) = advent_of_code::run_part($solver, $input);
+        advent_of_code::print_part($part, &outcome, elapsed);
     }};
 }
 
-pub fn read_file(folder: &str, day: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-
-    let filepath = cwd.join("src").join(folder).join(format!("{day:02}.txt"));
-
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+/// What a solver may return: the answer itself, an `Option` when there might not be one,
+/// or a `Result` of either when solving can fail, for example on malformed input.
+pub trait Solution {
+    /// `Ok(None)` when there is no answer
+    fn answer(self) -> Result<Option<String>, String>;
 }
 
-pub fn read_example(example: &str) -> String {
-    let cwd = env::current_dir().unwrap();
+macro_rules! impl_solution {
+    ($($answer:ty),*) => {
+        $(impl Solution for $answer {
+            fn answer(self) -> Result<Option<String>, String> {
+                Ok(Some(self.to_string()))
+            }
+        })*
+    };
+}
 
-    let filepath = cwd
-        .join("src")
-        .join("examples")
-        .join(format!("{example}.txt"));
+impl_solution!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, String, &str);
 
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+impl<T: Display> Solution for Option<T> {
+    fn answer(self) -> Result<Option<String>, String> {
+        Ok(self.map(|answer| answer.to_string()))
+    }
 }
 
-fn parse_time(val: &str, postfix: &str) -> f64 {
-    val.split(postfix).next().unwrap().parse().unwrap()
-}
-
-pub fn parse_exec_time(output: &str) -> f64 {
-    output.lines().fold(0_f64, |acc, l| {
-        if !l.contains("elapsed:") {
-            acc
-        } else {
-            let timing = l.split("(elapsed: ").last().unwrap();
-            // use `contains` istd. of `ends_with`: string may contain ANSI escape sequences.
-            // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-            if timing.contains("ns)") {
-                acc // range below rounding precision.
-            } else if timing.contains("µs)") {
-                acc + parse_time(timing, "µs") / 1000_f64
-            } else if timing.contains("ms)") {
-                acc + parse_time(timing, "ms")
-            } else if timing.contains("s)") {
-                acc + parse_time(timing, "s") * 1000_f64
-            } else {
-                acc
-            }
-        }
-    })
+impl<S: Solution, E: Display> Solution for Result<S, E> {
+    fn answer(self) -> Result<Option<String>, String> {
+        self.map_err(|error| error.to_string())?.answer()
+    }
 }
 
-/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-#[cfg(test)]
-macro_rules! assert_approx_eq {
-    ($a:expr, $b:expr) => {{
-        let (a, b) = (&$a, &$b);
-        assert!(
-            (*a - *b).abs() < 1.0e-6,
-            "{} is not approximately equal to {}",
-            *a,
-            *b
-        );
-    }};
+/// How solving a part went
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Outcome {
+    Solved(String),
+    /// The solver returned `None`
+    NotSolved,
+    /// The solver returned an error
+    Failed(String),
+    /// The solver panicked with this message
+    Panicked(String),
+    /// The solver didn't finish within this time
+    TimedOut(Duration),
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+thread_local! {
+    static SOLVING: Cell<bool> = const { Cell::new(false) };
+}
 
-    #[test]
-    fn test_parse_exec_time() {
-        assert_approx_eq!(
-            parse_exec_time(&format!(
-                "🎄 Part 1 🎄\n0 (elapsed: 74.13ns){}\n🎄 Part 2 🎄\n0 (elapsed: 50.00ns){}",
-                ANSI_RESET, ANSI_RESET
-            )),
-            0_f64
-        );
+/// Silences the panic hook on threads that are running a solver, whose panics are
+/// reported as `Outcome::Panicked` instead. Panics elsewhere still reach the old hook.
+fn quiet_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !SOLVING.with(Cell::get) {
+                hook(info);
+            }
+        }));
+    });
+}
 
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 755µs)\n🎄 Part 2 🎄\n0 (elapsed: 700µs)"),
-            1.455_f64
-        );
+/// Runs and times a solver, catching any panic so the other part still gets to run
+pub fn run_part<R: Solution>(solver: impl FnOnce(&str) -> R, input: &str) -> (Outcome, Duration) {
+    quiet_panic_hook();
+    let solving = SOLVING.with(|cell| cell.replace(true));
+    let timer = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| solver(input).answer()));
+    let elapsed = timer.elapsed();
+    SOLVING.with(|cell| cell.set(solving));
+    let outcome = match result {
+        Ok(Ok(Some(answer))) => Outcome::Solved(answer),
+        Ok(Ok(None)) => Outcome::NotSolved,
+        Ok(Err(error)) => Outcome::Failed(error),
+        Err(payload) => Outcome::Panicked(panic_message(payload)),
+    };
+    (outcome, elapsed)
+}
 
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 70µs)\n🎄 Part 2 🎄\n0 (elapsed: 1.45ms)"),
-            1.52_f64
-        );
+/// Runs a part on its own thread and gives up on it after `timeout`. The thread is then
+/// cancelled, see `helpers::cancelled`, and left to finish on its own.
+pub fn run_with_timeout(
+    run: fn(&str) -> (Outcome, Duration),
+    input: &str,
+    timeout: Duration,
+) -> (Outcome, Duration) {
+    let cancellation = Cancellation::default();
+    let (sender, receiver) = mpsc::channel();
+    let (token, input) = (cancellation.clone(), input.to_string());
+
+    let spawned = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            token.install();
+            // Nobody is listening anymore after a timeout
+            let _ = sender.send(run(&input));
+        });
+    if let Err(e) = spawned {
+        let error = format!("could not spawn a thread: {e}");
+        return (Outcome::Failed(error), Duration::ZERO);
+    }
 
-        assert_approx_eq!(
-            parse_exec_time(
-                "🎄 Part 1 🎄\n0 (elapsed: 10.3s)\n🎄 Part 2 🎄\n0 (elapsed: 100.50ms)"
-            ),
-            10400.50_f64
-        );
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => {
+            cancellation.cancel();
+            (Outcome::TimedOut(timeout), timeout)
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            let message = "unknown cause".to_string();
+            (Outcome::Panicked(message), Duration::ZERO)
+        }
     }
 }
 
-pub mod aoc_cli {
-    use std::{
-        fmt::Display,
-        fs::create_dir_all,
-        process::{Command, Output, Stdio},
-    };
-
-    pub enum AocCliError {
-        CommandNotFound,
-        CommandNotCallable,
-        BadExitStatus(Output),
-        IoError,
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown cause".to_string(),
+        },
     }
+}
 
-    impl Display for AocCliError {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            match self {
-                AocCliError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-                AocCliError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-                AocCliError::BadExitStatus(_) => {
-                    write!(f, "aoc-cli exited with a non-zero status.")
-                }
-                AocCliError::IoError => write!(f, "could not write output files to file system."),
-            }
+pub fn print_part(part: u8, outcome: &Outcome, elapsed: Duration) {
+    println!("🎄 {ANSI_BOLD}Part {part}{ANSI_RESET} 🎄");
+    match outcome {
+        Outcome::Solved(result) => {
+            println!("{result} {ANSI_ITALIC}(elapsed: {elapsed:.2?}){ANSI_RESET}")
+        }
+        Outcome::NotSolved => println!("not solved."),
+        Outcome::Failed(error) => println!("{ANSI_RED}{FAILED}{ANSI_RESET} {error}"),
+        Outcome::Panicked(message) => println!("{ANSI_RED}{PANICKED}{ANSI_RESET} {message}"),
+        Outcome::TimedOut(timeout) => {
+            println!("{ANSI_RED}{TIMED_OUT}{ANSI_RESET} gave up after {timeout:.2?}")
         }
     }
+}
 
-    pub fn check() -> Result<(), AocCliError> {
-        Command::new("aoc")
-            .arg("-V")
-            .output()
-            .map_err(|_| AocCliError::CommandNotFound)?;
-        Ok(())
-    }
+/// A path relative to the crate root, so that it doesn't matter where commands run from
+pub fn manifest_path(path: impl AsRef<Path>) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(path)
+}
 
-    pub fn read(day: u8, year: Option<u16>) -> Result<Output, AocCliError> {
-        // TODO: output local puzzle if present.
-        let args = build_args("read", &[], day, year);
-        call_aoc_cli(&args)
+/// Parses the file at `path`, or gives the default if there is no file yet
+pub fn parse_file_or_default<T>(path: &Path) -> io::Result<T>
+where
+    T: FromStr<Err = io::Error> + Default,
+{
+    match fs::read_to_string(path) {
+        Ok(contents) => contents.parse(),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(e),
     }
+}
 
-    pub fn download(day: u8, year: Option<u16>) -> Result<Output, AocCliError> {
-        let input_path = get_input_path(day);
-
-        let puzzle_path = get_puzzle_path(day);
-        create_dir_all("src/puzzles").map_err(|_| AocCliError::IoError)?;
-
-        let args = build_args(
-            "download",
-            &[
-                "--overwrite".into(),
-                "--input-file".into(),
-                input_path.to_string(),
-                "--puzzle-file".into(),
-                puzzle_path.to_string(),
-            ],
-            day,
-            year,
-        );
+pub fn read_file(folder: &str, day: u8) -> String {
+    let filepath = manifest_path("src")
+        .join(folder)
+        .join(format!("{day:02}.txt"));
 
-        let output = call_aoc_cli(&args)?;
+    let f = fs::read_to_string(filepath);
+    f.expect("could not open input file")
+}
 
-        if output.status.success() {
-            println!("---");
-            println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-            println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-            Ok(output)
-        } else {
-            Err(AocCliError::BadExitStatus(output))
-        }
-    }
+pub fn read_example(example: &str) -> String {
+    let filepath = manifest_path("src")
+        .join("examples")
+        .join(format!("{example}.txt"));
 
-    fn get_input_path(day: u8) -> String {
-        let day_padded = format!("{day:02}");
-        format!("src/inputs/{day_padded}.txt")
-    }
+    let f = fs::read_to_string(filepath);
+    f.expect("could not open input file")
+}
+
+/// Where a day's input comes from, see `--input`
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub enum InputSource {
+    /// `src/inputs/NN.txt`
+    #[default]
+    Default,
+    Path(PathBuf),
+    /// Passed as `-`
+    Stdin,
+}
+
+impl FromStr for InputSource {
+    type Err = Infallible;
 
-    fn get_puzzle_path(day: u8) -> String {
-        let day_padded = format!("{day:02}");
-        format!("src/puzzles/{day_padded}.md")
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "-" => InputSource::Stdin,
+            path => InputSource::Path(PathBuf::from(path)),
+        })
     }
+}
 
-    fn build_args(command: &str, args: &[String], day: u8, year: Option<u16>) -> Vec<String> {
-        let mut cmd_args = args.to_vec();
+/// An input that couldn't be read
+#[derive(Debug)]
+pub struct InputError {
+    pub day: u8,
+    pub source: InputSource,
+    pub error: io::Error,
+}
 
-        if let Some(year) = year {
-            cmd_args.push("--year".into());
-            cmd_args.push(year.to_string());
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.source {
+            InputSource::Stdin => write!(f, "could not read the input from stdin: {}", self.error),
+            InputSource::Path(path) => {
+                write!(
+                    f,
+                    "could not read input file \"{}\": {}",
+                    path.display(),
+                    self.error
+                )
+            }
+            InputSource::Default => {
+                let path = input_path(self.day);
+                write!(
+                    f,
+                    "could not read input file \"{}\": {}",
+                    path.display(),
+                    self.error
+                )?;
+                if self.error.kind() == ErrorKind::NotFound {
+                    write!(
+                        f,
+                        ". Run `cargo download {}` to fetch it, or pass another file with `--input <path>`.",
+                        self.day
+                    )?;
+                }
+                Ok(())
+            }
         }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+fn input_path(day: u8) -> PathBuf {
+    manifest_path("src")
+        .join("inputs")
+        .join(format!("{day:02}.txt"))
+}
+
+pub fn read_input(day: u8, source: &InputSource) -> Result<String, InputError> {
+    let read = match source {
+        InputSource::Default => fs::read_to_string(input_path(day)),
+        InputSource::Path(path) => fs::read_to_string(path),
+        InputSource::Stdin => io::read_to_string(io::stdin()),
+    };
+    read.map_err(|error| InputError {
+        day,
+        source: source.clone(),
+        error,
+    })
+}
 
-        cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        cmd_args
+    #[test]
+    fn test_run_part() {
+        assert_eq!(
+            run_part(|_| Some(42), "").0,
+            Outcome::Solved("42".to_string())
+        );
+        assert_eq!(run_part(|_| None::<u32>, "").0, Outcome::NotSolved);
+        assert_eq!(
+            run_part(|input| input.parse::<u32>(), "x").0,
+            Outcome::Failed("invalid digit found in string".to_string())
+        );
+        assert_eq!(
+            run_part(|input| -> Option<u32> { panic!("no {input}") }, "answer").0,
+            Outcome::Panicked("no answer".to_string())
+        );
     }
 
-    fn call_aoc_cli(args: &[String]) -> Result<Output, AocCliError> {
-        if cfg!(debug_assertions) {
-            println!("Calling >aoc with: {}", args.join(" "));
-        }
+    #[test]
+    fn test_run_with_timeout() {
+        let timeout = Duration::from_millis(50);
+        let quick = |input: &str| run_part(|input| input.len(), input);
+        assert_eq!(
+            run_with_timeout(quick, "four", timeout).0,
+            Outcome::Solved("4".to_string())
+        );
 
-        Command::new("aoc")
-            .args(args)
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .output()
-            .map_err(|_| AocCliError::CommandNotCallable)
+        // Only stops because it polls for the cancellation
+        let forever = |input: &str| run_part(|_| helpers::cancellable(0..).find(|&i| i < 0), input);
+        assert_eq!(
+            run_with_timeout(forever, "", timeout),
+            (Outcome::TimedOut(timeout), timeout)
+        );
     }
 }
@@ -2,44 +2,187 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-use std::process::Command;
+use advent_of_code::days::{Day, Options, Part, Selection, DAYS};
+use advent_of_code::report::{Format, Record, Reporter};
+use advent_of_code::{answers, baseline};
+use advent_of_code::{
+    InputSource, Outcome, ANSI_BOLD, ANSI_ITALIC, ANSI_RED, ANSI_RESET, STACK_SIZE,
+};
+use std::collections::BTreeMap;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
 
 fn main() {
-    let total: f64 = (1..=25)
+    let Args {
+        options,
+        jobs,
+        days,
+        skip_slow,
+        only_unsolved,
+    } = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+    let answers = answers::load();
+
+    let work: Vec<(&Day, Vec<&Part>)> = DAYS
+        .iter()
+        .filter(|day| days.as_ref().is_none_or(|days| days.contains(day.day)))
+        .filter(|day| !(skip_slow && day.slow))
         .map(|day| {
-            let day = format!("{day:02}");
+            let parts = day
+                .selected(&options)
+                .filter(|part| !only_unsolved || answers.get(day.day, part.part).is_none())
+                .collect();
+            (day, parts)
+        })
+        .filter(|(_, parts): &(_, Vec<_>)| !parts.is_empty())
+        .collect();
 
-            let mut args = vec!["run", "--bin", &day];
-            if cfg!(not(debug_assertions)) {
-                args.push("--release");
-            }
+    if options.input != InputSource::Default && work.len() > 1 {
+        eprintln!("{ANSI_RED}error:{ANSI_RESET} --input needs a single day, e.g. `cargo all 5 --input <path>`");
+        process::exit(1);
+    }
 
-            let cmd = Command::new("cargo").args(&args).output().unwrap();
+    let mut reporter = Reporter::new(options.format);
+    let (mut total, mut failures) = (Duration::ZERO, 0);
+    let mut all = Vec::new();
 
-            println!("----------");
-            println!("{ANSI_BOLD}| Day {day} |{ANSI_RESET}");
-            println!("----------");
+    let (sender, receiver) = mpsc::channel();
+    let next = AtomicUsize::new(0);
 
-            let output = String::from_utf8(cmd.stdout).unwrap();
-            let is_empty = output.is_empty();
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let (sender, next, work, options) = (sender.clone(), &next, &work, &options);
+            thread::Builder::new()
+                .stack_size(STACK_SIZE)
+                .spawn_scoped(scope, move || loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some((day, parts)) = work.get(index) else {
+                        break;
+                    };
+                    if sender.send((index, run(day, parts, options))).is_err() {
+                        break;
+                    }
+                })
+                .expect("could not spawn a worker thread");
+        }
+        drop(sender);
 
-            println!(
-                "{}",
-                if is_empty {
-                    "Not solved."
-                } else {
-                    output.trim()
+        // Days finish in any order, but are printed in the order they are registered
+        let mut finished = BTreeMap::new();
+        let mut printed = 0;
+        for (index, records) in receiver {
+            finished.insert(index, records);
+            while let Some(mut records) = finished.remove(&printed) {
+                reporter.day(work[printed].0.day);
+                printed += 1;
+                if options.input == InputSource::Default {
+                    answers.mark(&mut records);
                 }
-            );
 
-            if is_empty {
-                0_f64
-            } else {
-                advent_of_code::parse_exec_time(&output)
+                for record in &records {
+                    reporter.record(record);
+                    if let Outcome::Solved(_) = record.outcome {
+                        total += record.elapsed;
+                    }
+                    if record.is_failure() {
+                        failures += 1;
+                    }
+                }
+                all.extend(records);
             }
-        })
-        .sum();
+        }
+    });
+
+    if reporter.format() == Format::Text {
+        println!(
+            "{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{:.2}ms{ANSI_RESET}",
+            total.as_secs_f64() * 1000.0
+        );
+        if failures > 0 {
+            println!("{ANSI_RED}{failures} failed{ANSI_RESET}");
+        }
+    }
+    reporter.finish();
+
+    let regressed = baseline::check(&all, &options);
+    let changed = answers::check(answers, &all, &options);
+    if regressed || changed {
+        process::exit(1);
+    }
+}
+
+struct Args {
+    options: Options,
+    /// How many days to solve at once
+    jobs: usize,
+    /// All days if not given
+    days: Option<Selection>,
+    skip_slow: bool,
+    /// Skips parts with a recorded answer
+    only_unsolved: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let options = Options::from_args(&mut args)?;
+    let jobs = match (
+        args.opt_value_from_str("--jobs")?,
+        args.contains("--sequential"),
+    ) {
+        (_, true) => 1,
+        (Some(jobs), false) => jobs,
+        // Benchmarks are only comparable when nothing else runs alongside them
+        (None, false) if options.bench.is_some() => 1,
+        (None, false) => thread::available_parallelism().map_or(1, usize::from),
+    };
+    let skip_slow = args.contains("--skip-slow");
+    let only_unsolved = args.contains("--only-unsolved");
+    let days = match args.finish().as_slice() {
+        [] => None,
+        [days] if !days.to_string_lossy().starts_with('-') => Some(
+            days.to_string_lossy()
+                .parse()
+                .map_err(|cause| pico_args::Error::ArgumentParsingFailed { cause })?,
+        ),
+        unused => {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: format!("unexpected arguments {unused:?}"),
+            })
+        }
+    };
+
+    Ok(Args {
+        options,
+        jobs: jobs.max(1),
+        days,
+        skip_slow,
+        only_unsolved,
+    })
+}
 
-    println!("{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total:.2}ms{ANSI_RESET}");
+/// Solves some parts of a day, every part fails if the input can't be read
+fn run(day: &Day, parts: &[&Part], options: &Options) -> Vec<Record> {
+    match advent_of_code::read_input(day.day, &options.input) {
+        Ok(input) => day.run_parts(parts.iter().copied(), &input, options),
+        Err(error) => parts
+            .iter()
+            .map(|part| Record {
+                day: day.day,
+                part: part.part,
+                solver: part.name,
+                outcome: Outcome::Failed(error.to_string()),
+                elapsed: Duration::ZERO,
+                stats: None,
+                expected: None,
+            })
+            .collect(),
+    }
 }
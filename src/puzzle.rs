@@ -0,0 +1,378 @@
+/*
+ * Renders the puzzle descriptions in `src/puzzles` for the terminal.
+ */
+use crate::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::env;
+
+const PART_TWO: &str = "\\--- Part Two ---";
+
+/// Starts the line with the answer given to a solved part, which is no example's answer
+const ANSWERED: &str = "Your puzzle answer was";
+
+/// Lines from where the page stops describing the puzzle
+const FOOTERS: [&str; 6] = [
+    "Both parts of this puzzle are complete!",
+    "At this point, you should [return to your Advent calendar]",
+    "If you still want to see it, you can [get your puzzle input]",
+    "To begin, [get your puzzle input]",
+    "Answer:",
+    "You can also [Share",
+];
+
+/// The description of one part, or of both if `part` is `None`.
+/// `None` if the part isn't in the description, e.g. because it wasn't unlocked when it was downloaded.
+pub fn section(markdown: &str, part: Option<u8>) -> Option<String> {
+    let lines: Vec<_> = markdown
+        .lines()
+        .skip_while(|line| !line.starts_with("\\--- Day"))
+        .take_while(|line| !FOOTERS.iter().any(|footer| line.starts_with(footer)))
+        .collect();
+    let part_two = lines.iter().position(|line| *line == PART_TWO);
+    let lines = match (part, part_two) {
+        (None, _) => &lines[..],
+        (Some(1), Some(part_two)) => &lines[..part_two],
+        (Some(1), None) => &lines[..],
+        (Some(2), Some(part_two)) => &lines[part_two..],
+        _ => return None,
+    };
+    Some(lines.join("\n").trim().to_string())
+}
+
+/// A code block in the description, with the answers it might give
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Example {
+    /// The part whose description the block is in
+    pub part: u8,
+    pub input: String,
+    /// Inline code and emphasized words between this block and the next, last first,
+    /// as examples are usually followed by the answer they give
+    pub answers: Vec<String>,
+}
+
+/// Every code block in the description
+pub fn examples(markdown: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = vec![];
+    let mut part = 1;
+    let mut code_block: Option<(usize, Vec<&str>)> = None;
+    let mut prose: Vec<&str> = vec![];
+
+    let finish = |examples: &mut Vec<Example>, prose: &mut Vec<&str>| {
+        if let Some(example) = examples.last_mut().filter(|e| e.answers.is_empty()) {
+            example.answers = answers(&prose.join(" "));
+        }
+        prose.clear();
+    };
+
+    let description = section(markdown, None).unwrap_or_default();
+    for line in description.lines() {
+        let fence = line.trim_start().starts_with("```");
+        match &mut code_block {
+            Some((_, lines)) if !fence => lines.push(line),
+            Some((indent, lines)) => {
+                let indent = *indent;
+                let lines: Vec<_> = lines
+                    .iter()
+                    .map(|line| line.get(indent..).unwrap_or(line.trim_start()))
+                    .collect();
+                let input = lines.join("\n").trim_end().to_string() + "\n";
+                examples.push(Example {
+                    part,
+                    input,
+                    answers: vec![],
+                });
+                code_block = None;
+            }
+            None if fence => {
+                finish(&mut examples, &mut prose);
+                code_block = Some((line.len() - line.trim_start().len(), vec![]));
+            }
+            None if line == PART_TWO => {
+                finish(&mut examples, &mut prose);
+                part = 2;
+            }
+            None if line.starts_with(ANSWERED) => {}
+            None => prose.push(line),
+        }
+    }
+    finish(&mut examples, &mut prose);
+    examples
+}
+
+/// Inline code like `` `42` `` and emphasized words like `*42*`, last first and without repeats
+fn answers(prose: &str) -> Vec<String> {
+    let mut answers: Vec<String> = vec![];
+    let mut rest = prose;
+    while let Some(start) = rest.find(['`', '*']) {
+        let delimiter = &rest[start..start + 1];
+        let Some((span, after)) = rest[start + 1..].split_once(delimiter) else {
+            break;
+        };
+        let answer = span.trim_matches(['*', '`']);
+        let is_word = delimiter == "`" || !answer.contains(' ');
+        if !answer.is_empty() && is_word && !answers.iter().any(|a| a == answer) {
+            answers.push(answer.to_string());
+        }
+        rest = after;
+    }
+    answers.reverse();
+    answers
+}
+
+/// The width to wrap at: that of the terminal, else `$COLUMNS`, else 80 columns
+pub fn terminal_width() -> usize {
+    terminal_size::terminal_size()
+        .map(|(terminal_size::Width(width), _)| width as usize)
+        .or_else(|| env::var("COLUMNS").ok()?.parse().ok())
+        .unwrap_or(80)
+}
+
+/// Headings and emphasis in bold, inline code in italics, links as their text.
+/// Paragraphs and list items are wrapped at `width`, code blocks are left as they are.
+pub fn render(markdown: &str, width: usize) -> String {
+    let mut rendered = String::new();
+    let mut paragraph: Vec<&str> = vec![];
+    let mut list_item = false;
+    let mut code_block: Option<usize> = None;
+
+    let flush = |paragraph: &mut Vec<&str>, list_item: &mut bool, rendered: &mut String| {
+        if !paragraph.is_empty() {
+            let text = inline(&paragraph.join(" "));
+            if *list_item {
+                rendered.push_str(&wrap(&text, width, "• ", "  "));
+            } else {
+                rendered.push_str(&wrap(&text, width, "", ""));
+            }
+            paragraph.clear();
+        }
+        *list_item = false;
+    };
+
+    for line in markdown.lines() {
+        let indent = line.len() - line.trim_start().len();
+        if let Some(fence) = code_block {
+            if line.trim_start().starts_with("```") {
+                code_block = None;
+            } else {
+                let code = line.get(fence..).unwrap_or(line.trim_start());
+                rendered.push_str(&format!("    {code}\n"));
+            }
+        } else if line.trim_start().starts_with("```") {
+            flush(&mut paragraph, &mut list_item, &mut rendered);
+            code_block = Some(indent);
+        } else if line.trim().is_empty() {
+            flush(&mut paragraph, &mut list_item, &mut rendered);
+            if !rendered.is_empty() && !rendered.ends_with("\n\n") {
+                rendered.push('\n');
+            }
+        } else if !paragraph.is_empty() && line.len() >= 3 && line.chars().all(|c| c == '-') {
+            let heading = inline(&paragraph.join(" "));
+            rendered.push_str(&format!("{ANSI_BOLD}{heading}{ANSI_RESET}\n"));
+            paragraph.clear();
+        } else if let Some(item) = line.strip_prefix("* ") {
+            flush(&mut paragraph, &mut list_item, &mut rendered);
+            paragraph.push(item);
+            list_item = true;
+        } else {
+            paragraph.push(line.trim());
+        }
+    }
+    flush(&mut paragraph, &mut list_item, &mut rendered);
+    rendered.trim_end().to_string() + "\n"
+}
+
+/// Renders the inline markup of one paragraph
+fn inline(text: &str) -> String {
+    let mut rendered = String::new();
+    let (mut emphasis, mut code) = (false, false);
+    let style = |emphasis: bool, code: bool| {
+        let mut style = ANSI_RESET.to_string();
+        if emphasis {
+            style.push_str(ANSI_BOLD);
+        }
+        if code {
+            style.push_str(ANSI_ITALIC);
+        }
+        style
+    };
+
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '`' => {
+                code = !code;
+                rendered.push_str(&style(emphasis, code));
+            }
+            _ if code => rendered.push(c),
+            '\\' if chars
+                .peek()
+                .is_some_and(|(_, next)| next.is_ascii_punctuation()) =>
+            {
+                rendered.push(chars.next().unwrap().1);
+            }
+            '*' => {
+                emphasis = !emphasis;
+                rendered.push_str(&style(emphasis, code));
+            }
+            '[' => match link(&text[i..]) {
+                Some((label, length)) => {
+                    rendered.push_str(&inline(label));
+                    while chars.peek().is_some_and(|(j, _)| *j < i + length) {
+                        chars.next();
+                    }
+                }
+                None => rendered.push(c),
+            },
+            _ => rendered.push(c),
+        }
+    }
+    if emphasis || code {
+        rendered.push_str(ANSI_RESET);
+    }
+    rendered
+}
+
+/// The label of a `[label](url)` at the start of `text`, and the length of the whole link
+fn link(text: &str) -> Option<(&str, usize)> {
+    let (label, rest) = text[1..].split_once("](")?;
+    let (_, after) = rest.split_once(')')?;
+    Some((label, text.len() - after.len()))
+}
+
+/// Wraps at spaces so that no line is wider than `width`, unless a single word is.
+/// The first line starts with `first`, every other with `rest`.
+fn wrap(text: &str, width: usize, first: &str, rest: &str) -> String {
+    let mut wrapped = String::from(first);
+    let mut column = first.chars().count();
+    let mut empty = true;
+    for word in text.split(' ').filter(|word| !word.is_empty()) {
+        let length = visible_len(word);
+        if !empty && column + 1 + length > width {
+            wrapped.push('\n');
+            wrapped.push_str(rest);
+            column = rest.chars().count();
+            empty = true;
+        }
+        if !empty {
+            wrapped.push(' ');
+            column += 1;
+        }
+        wrapped.push_str(word);
+        column += length;
+        empty = false;
+    }
+    wrapped.push('\n');
+    wrapped
+}
+
+/// Characters that take up space, leaving out ANSI escape sequences
+fn visible_len(text: &str) -> usize {
+    let mut length = 0;
+    let mut escape = false;
+    for c in text.chars() {
+        match c {
+            '\x1b' => escape = true,
+            'm' if escape => escape = false,
+            _ if escape => {}
+            _ => length += 1,
+        }
+    }
+    length
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "article \\*[title]{border-bottom:1px dotted #ffff66;}
+
+\\--- Day 8: Two-Factor Authentication ---
+----------
+
+The screen is *`50` pixels wide*, see [little screen](https://example.com).
+
+* `rect 3x2` creates a rectangle:
+
+  ```
+  ###....
+  .......
+  ```
+
+Your puzzle answer was `115`.
+
+\\--- Part Two ---
+----------
+
+*What code is the screen trying to display?*
+
+Both parts of this puzzle are complete! They provide two gold stars: \\*\\*
+";
+
+    #[test]
+    fn test_section() {
+        let part_one = section(PUZZLE, Some(1)).unwrap();
+        assert!(part_one.starts_with("\\--- Day 8"));
+        assert!(part_one.ends_with("`115`."));
+        let part_two = section(PUZZLE, Some(2)).unwrap();
+        assert_eq!(
+            part_two,
+            "\\--- Part Two ---\n----------\n\n*What code is the screen trying to display?*"
+        );
+        assert!(section(PUZZLE, None).unwrap().ends_with("display?*"));
+        assert_eq!(section(&part_one, Some(2)), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let rendered = render(&section(PUZZLE, Some(1)).unwrap(), 80);
+        assert_eq!(
+            rendered,
+            format!(
+                "{ANSI_BOLD}--- Day 8: Two-Factor Authentication ---{ANSI_RESET}\n\
+                 \n\
+                 The screen is {ANSI_RESET}{ANSI_BOLD}{ANSI_RESET}{ANSI_BOLD}{ANSI_ITALIC}50\
+                 {ANSI_RESET}{ANSI_BOLD} pixels wide{ANSI_RESET}, see little screen.\n\
+                 \n\
+                 • {ANSI_RESET}{ANSI_ITALIC}rect 3x2{ANSI_RESET} creates a rectangle:\n\
+                 \n    ###....\n    .......\n\
+                 \n\
+                 Your puzzle answer was {ANSI_RESET}{ANSI_ITALIC}115{ANSI_RESET}.\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_examples() {
+        let markdown = format!(
+            "{PUZZLE}\n\
+             Next, `rect 1x1` turns on:\n\n```\n#.\n\n```\n\n\
+             That lights `1` pixel; *`1`*, not *two pixels*.\n"
+        );
+        assert_eq!(
+            examples(&markdown),
+            [Example {
+                part: 1,
+                input: "###....\n.......\n".to_string(),
+                answers: vec![],
+            }]
+        );
+
+        let part_two = markdown
+            .replace("Both parts", "Unlocked")
+            .replace("Answer:", "");
+        let examples = examples(&part_two);
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[1].part, 2);
+        assert_eq!(examples[1].input, "#.\n");
+        assert_eq!(examples[1].answers, ["1"]);
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(
+            wrap("one two three four", 9, "• ", "  "),
+            "• one two\n  three\n  four\n"
+        );
+        assert_eq!(wrap("one two", 5, "", ""), "one\ntwo\n");
+        assert_eq!(visible_len(&format!("{ANSI_BOLD}50{ANSI_RESET}")), 2);
+    }
+}
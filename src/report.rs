@@ -0,0 +1,346 @@
+/*
+ * Prints how every part went, either as text for humans or as JSON or CSV records for scripts.
+ */
+use crate::answers::Verdict;
+use crate::bench::Stats;
+use crate::{print_part, Outcome, ANSI_BOLD, ANSI_GREEN, ANSI_ITALIC, ANSI_RED, ANSI_RESET};
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("expected json, csv or text, got \"{s}\"")),
+        }
+    }
+}
+
+/// How solving one part went
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    /// The solver's function name, since a part may have several
+    pub solver: &'static str,
+    pub outcome: Outcome,
+    /// The median when benchmarking
+    pub elapsed: Duration,
+    /// Only when benchmarking
+    pub stats: Option<Stats>,
+    /// The recorded answer, if there is one
+    pub expected: Option<String>,
+}
+
+impl Record {
+    /// A record of `part_one` or `part_two` for tests
+    #[cfg(test)]
+    pub(crate) fn new(day: u8, part: u8, outcome: Outcome, elapsed: Duration) -> Record {
+        Record {
+            day,
+            part,
+            solver: if part == 1 { "part_one" } else { "part_two" },
+            outcome,
+            elapsed,
+            stats: None,
+            expected: None,
+        }
+    }
+
+    pub fn status(&self) -> &'static str {
+        match self.outcome {
+            Outcome::Solved(_) => "solved",
+            Outcome::NotSolved => "not_solved",
+            Outcome::Failed(_) => "failed",
+            Outcome::Panicked(_) => "panicked",
+            Outcome::TimedOut(_) => "timed_out",
+        }
+    }
+
+    pub fn answer(&self) -> Option<&str> {
+        match &self.outcome {
+            Outcome::Solved(answer) => Some(answer),
+            _ => None,
+        }
+    }
+
+    /// Why the part failed, panicked or timed out
+    pub fn error(&self) -> Option<String> {
+        match &self.outcome {
+            Outcome::Failed(message) | Outcome::Panicked(message) => Some(message.clone()),
+            Outcome::TimedOut(timeout) => Some(format!("gave up after {timeout:.2?}")),
+            _ => None,
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        self.error().is_some()
+    }
+
+    pub fn verdict(&self) -> Verdict {
+        match &self.expected {
+            None => Verdict::Unknown,
+            Some(expected) if self.answer() == Some(expected) => Verdict::Matches,
+            Some(_) => Verdict::Differs,
+        }
+    }
+
+    fn verdict_name(&self) -> &'static str {
+        match self.verdict() {
+            Verdict::Matches => "matches",
+            Verdict::Differs => "differs",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    /// ✔, ✘ with the recorded answer, or ?
+    fn mark(&self) -> String {
+        match (self.verdict(), &self.expected) {
+            (Verdict::Matches, _) => format!("{ANSI_GREEN}✔{ANSI_RESET}"),
+            (Verdict::Differs, Some(expected)) => {
+                format!("{ANSI_RED}✘ (recorded: {expected}){ANSI_RESET}")
+            }
+            _ => "?".to_string(),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let string = |value: Option<&str>| value.map_or("null".to_string(), json_string);
+        let stats = self.stats.map_or("null".to_string(), |stats| {
+            format!(
+                r#"{{"runs":{},"min_ns":{},"median_ns":{},"mean_ns":{},"p95_ns":{},"stddev_ns":{}}}"#,
+                stats.runs,
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.mean.as_nanos(),
+                stats.p95.as_nanos(),
+                stats.stddev.as_nanos()
+            )
+        });
+        format!(
+            r#"{{"day":{},"part":{},"solver":{},"status":{},"answer":{},"expected":{},"verdict":{},"error":{},"elapsed_ns":{},"stats":{}}}"#,
+            self.day,
+            self.part,
+            json_string(self.solver),
+            json_string(self.status()),
+            string(self.answer()),
+            string(self.expected.as_deref()),
+            json_string(self.verdict_name()),
+            string(self.error().as_deref()),
+            self.elapsed.as_nanos(),
+            stats
+        )
+    }
+
+    fn to_csv(&self) -> String {
+        [
+            self.day.to_string(),
+            self.part.to_string(),
+            self.solver.to_string(),
+            self.status().to_string(),
+            self.answer().unwrap_or_default().to_string(),
+            self.expected.clone().unwrap_or_default(),
+            self.verdict_name().to_string(),
+            self.error().unwrap_or_default(),
+            self.elapsed.as_nanos().to_string(),
+        ]
+        .into_iter()
+        .chain(match self.stats {
+            Some(stats) => [
+                stats.runs.to_string(),
+                stats.min.as_nanos().to_string(),
+                stats.median.as_nanos().to_string(),
+                stats.mean.as_nanos().to_string(),
+                stats.p95.as_nanos().to_string(),
+                stats.stddev.as_nanos().to_string(),
+            ],
+            None => Default::default(),
+        })
+        .map(|field| csv_field(&field))
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+}
+
+const CSV_HEADER: &str =
+    "day,part,solver,status,answer,expected,verdict,error,elapsed_ns,runs,min_ns,median_ns,mean_ns,p95_ns,stddev_ns";
+
+fn json_string(value: &str) -> String {
+    let mut json = String::from('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Prints records as they come in, JSON records are wrapped in an array
+pub struct Reporter {
+    format: Format,
+    records: usize,
+}
+
+impl Reporter {
+    pub fn new(format: Format) -> Self {
+        match format {
+            Format::Text => {}
+            Format::Json => print!("["),
+            Format::Csv => println!("{CSV_HEADER}"),
+        }
+        Reporter { format, records: 0 }
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// A heading in front of a day's parts, only printed as text
+    pub fn day(&self, day: u8) {
+        if self.format == Format::Text {
+            println!("----------");
+            println!("{ANSI_BOLD}| Day {day:02} |{ANSI_RESET}");
+            println!("----------");
+        }
+    }
+
+    pub fn record(&mut self, record: &Record) {
+        match self.format {
+            Format::Text => match (&record.outcome, record.stats) {
+                (Outcome::Solved(answer), stats) => {
+                    let timing = match stats {
+                        Some(stats) => format!(
+                            "median: {:.2?}, mean: {:.2?} ± {:.2?}, min: {:.2?}, p95: {:.2?}, runs: {}",
+                            stats.median, stats.mean, stats.stddev, stats.min, stats.p95, stats.runs
+                        ),
+                        None => format!("elapsed: {:.2?}", record.elapsed),
+                    };
+                    println!("🎄 {ANSI_BOLD}Part {}{ANSI_RESET} 🎄", record.part);
+                    println!(
+                        "{answer} {} {ANSI_ITALIC}({timing}){ANSI_RESET}",
+                        record.mark()
+                    );
+                }
+                (outcome, _) => {
+                    print_part(record.part, outcome, record.elapsed);
+                    if record.verdict() == Verdict::Differs {
+                        println!("{}", record.mark());
+                    }
+                }
+            },
+            Format::Json => {
+                let separator = if self.records == 0 { "" } else { "," };
+                print!("{separator}\n  {}", record.to_json())
+            }
+            Format::Csv => println!("{}", record.to_csv()),
+        }
+        self.records += 1;
+    }
+
+    pub fn finish(self) {
+        if self.format == Format::Json {
+            println!("{}]", if self.records == 0 { "" } else { "\n" });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(
+            Record::new(
+                8,
+                2,
+                Outcome::Solved("#.\n\"#".to_string()),
+                Duration::from_micros(15)
+            )
+            .to_json(),
+            r##"{"day":8,"part":2,"solver":"part_two","status":"solved","answer":"#.\n\"#","expected":null,"verdict":"unknown","error":null,"elapsed_ns":15000,"stats":null}"##
+        );
+        assert_eq!(
+            Record::new(8, 2, Outcome::NotSolved, Duration::from_micros(15)).to_json(),
+            r#"{"day":8,"part":2,"solver":"part_two","status":"not_solved","answer":null,"expected":null,"verdict":"unknown","error":null,"elapsed_ns":15000,"stats":null}"#
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        let samples = [3, 1, 2].map(Duration::from_micros);
+        let benched = Record {
+            stats: Stats::new(&samples),
+            ..Record::new(
+                8,
+                2,
+                Outcome::Solved("42".to_string()),
+                Duration::from_micros(15),
+            )
+        };
+        assert!(benched.to_json().ends_with(
+            r#""stats":{"runs":3,"min_ns":1000,"median_ns":2000,"mean_ns":2000,"p95_ns":3000,"stddev_ns":816}}"#
+        ));
+        assert_eq!(
+            benched.to_csv(),
+            "8,2,part_two,solved,42,,unknown,,15000,3,1000,2000,2000,3000,816"
+        );
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(
+            Record::new(
+                8,
+                2,
+                Outcome::Solved("42".to_string()),
+                Duration::from_micros(15)
+            )
+            .to_csv(),
+            "8,2,part_two,solved,42,,unknown,,15000,,,,,,"
+        );
+        assert_eq!(
+            Record::new(
+                8,
+                2,
+                Outcome::Failed("expected tag, \"rect\"".to_string()),
+                Duration::from_micros(15)
+            )
+            .to_csv(),
+            r#"8,2,part_two,failed,,,unknown,"expected tag, ""rect""",15000,,,,,,"#
+        );
+    }
+}